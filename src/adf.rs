@@ -0,0 +1,97 @@
+use serde_json::{Value, json};
+
+/// Wraps plain text in a minimal Atlassian Document Format document.
+pub fn description_to_adf(text: &str) -> Value {
+    json!({
+        "type": "doc",
+        "version": 1,
+        "content": [{
+            "type": "paragraph",
+            "content": [{
+                "type": "text",
+                "text": text
+            }]
+        }]
+    })
+}
+
+/// Puts an italic paragraph with `note` in front of an Atlassian Document Format document.
+pub fn prepend_note(note: &str, doc: &Value) -> Value {
+    let mut content = vec![json!({
+        "type": "paragraph",
+        "content": [{ "type": "text", "text": note, "marks": [{ "type": "em" }] }]
+    })];
+    if let Some(blocks) = doc.get("content").and_then(|c| c.as_array()) {
+        content.extend(blocks.iter().cloned());
+    }
+    json!({ "type": "doc", "version": 1, "content": content })
+}
+
+/// Flattens an Atlassian Document Format document to plain text, one line per block.
+pub fn adf_to_text(doc: &Value) -> String {
+    let mut out = String::new();
+    collect_text(doc, &mut out);
+    out.trim_end().to_string()
+}
+
+fn collect_text(node: &Value, out: &mut String) {
+    match node.get("type").and_then(|t| t.as_str()) {
+        Some("text") => {
+            if let Some(text) = node.get("text").and_then(|t| t.as_str()) {
+                out.push_str(text);
+            }
+        }
+        Some("hardBreak") => out.push('\n'),
+        Some("mention") => {
+            if let Some(text) = node.pointer("/attrs/text").and_then(|t| t.as_str()) {
+                out.push_str(text);
+            }
+        }
+        _ => {}
+    }
+    if let Some(children) = node.get("content").and_then(|c| c.as_array()) {
+        for child in children {
+            collect_text(child, out);
+        }
+    }
+    if matches!(
+        node.get("type").and_then(|t| t.as_str()),
+        Some("paragraph" | "heading" | "codeBlock" | "listItem" | "blockquote")
+    ) && !out.ends_with('\n')
+    {
+        out.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adf_round_trips_plain_text() {
+        assert_eq!(
+            adf_to_text(&description_to_adf("Rotated secrets")),
+            "Rotated secrets"
+        );
+    }
+
+    #[test]
+    fn adf_to_text_joins_blocks_with_newlines() {
+        let doc = json!({
+            "type": "doc",
+            "version": 1,
+            "content": [
+                { "type": "paragraph", "content": [
+                    { "type": "text", "text": "Ping " },
+                    { "type": "mention", "attrs": { "id": "abc", "text": "@Ada" } }
+                ]},
+                { "type": "bulletList", "content": [
+                    { "type": "listItem", "content": [
+                        { "type": "paragraph", "content": [{ "type": "text", "text": "one" }] }
+                    ]}
+                ]}
+            ]
+        });
+        assert_eq!(adf_to_text(&doc), "Ping @Ada\none");
+    }
+}
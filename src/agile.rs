@@ -0,0 +1,447 @@
+//! Boards and sprints through the Jira Software agile API (`/rest/agile/1.0`).
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value, json};
+
+use crate::client::{JiraClient, Request};
+use crate::error::{ErrorKind, JiraError};
+use crate::models::{Board, Issue, Sprint};
+
+/// The agile API caps sprint membership changes at 50 issues per call.
+const MAX_ISSUES_PER_MOVE: usize = 50;
+const PAGE_SIZE: u32 = 50;
+/// Fields fetched for issues listed on a sprint.
+const SPRINT_ISSUE_FIELDS: &str = "summary,status,assignee,issuetype";
+
+/// Fields for a new sprint; dates are ISO 8601 timestamps.
+#[derive(Debug, Clone, Default)]
+pub struct NewSprint {
+    pub board_id: u64,
+    pub name: String,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub goal: Option<String>,
+}
+
+/// Where `close_sprint_with_carry_over` puts issues that are not done.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum CarryOver {
+    /// The board's next future sprint
+    Next,
+    /// The backlog
+    Backlog,
+}
+
+/// What happened when a sprint was closed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintCloseReport {
+    /// The closed sprint; as it was before closing under `dry_run`.
+    pub sprint: Sprint,
+    /// The sprint open issues moved to; `None` when they went to the backlog.
+    pub moved_to: Option<Sprint>,
+    pub moved: Vec<String>,
+    pub completed: Vec<String>,
+}
+
+impl JiraClient {
+    /// Collects every page of an agile list endpoint (`startAt`/`maxResults` paging).
+    async fn agile_pages<T: DeserializeOwned>(
+        &self,
+        request: Request,
+        items: &str,
+    ) -> Result<Vec<T>> {
+        let mut all = Vec::new();
+        loop {
+            let page = self
+                .execute(
+                    request
+                        .clone()
+                        .query("startAt", all.len())
+                        .query("maxResults", PAGE_SIZE),
+                )
+                .await?;
+            let values = page.get(items).cloned().unwrap_or_else(|| json!([]));
+            let values: Vec<T> = serde_json::from_value(values)
+                .with_context(|| format!("Failed to parse agile {items} page"))?;
+            let fetched = values.len();
+            all.extend(values);
+            let is_last = page
+                .get("isLast")
+                .and_then(Value::as_bool)
+                .unwrap_or_else(|| {
+                    let total = page.get("total").and_then(Value::as_u64);
+                    total.is_none_or(|total| all.len() as u64 >= total)
+                });
+            if is_last || fetched == 0 {
+                return Ok(all);
+            }
+        }
+    }
+
+    pub async fn list_boards(&self, project: Option<&str>) -> Result<Vec<Board>> {
+        let mut request = Request::get("board").agile().action("list boards");
+        if let Some(project) = project {
+            request = request.query("projectKeyOrId", project);
+        }
+        self.agile_pages(request, "values").await
+    }
+
+    /// Lists a board's sprints, optionally only those in the given states (`future`, `active`, `closed`).
+    pub async fn list_sprints(&self, board_id: u64, states: &[String]) -> Result<Vec<Sprint>> {
+        let mut request = Request::get(format!("board/{board_id}/sprint"))
+            .agile()
+            .action("list sprints");
+        if !states.is_empty() {
+            request = request.query("state", states.join(","));
+        }
+        self.agile_pages(request, "values").await
+    }
+
+    pub async fn get_sprint(&self, sprint_id: u64) -> Result<Sprint> {
+        self.execute_as(
+            Request::get(format!("sprint/{sprint_id}"))
+                .agile()
+                .action("get sprint"),
+        )
+        .await
+    }
+
+    pub async fn sprint_issues(&self, sprint_id: u64) -> Result<Vec<Issue>> {
+        let request = Request::get(format!("sprint/{sprint_id}/issue"))
+            .agile()
+            .query("fields", SPRINT_ISSUE_FIELDS)
+            .action("list sprint issues");
+        self.agile_pages(request, "issues").await
+    }
+
+    /// Runs a request that creates or changes a sprint and reads the sprint
+    /// Jira returns; `None` under `dry_run`, where nothing comes back.
+    async fn write_sprint(&self, request: Request) -> Result<Option<Sprint>> {
+        if self.dry_run {
+            self.execute(request).await?;
+            return Ok(None);
+        }
+        self.execute_as(request).await.map(Some)
+    }
+
+    /// Creates a future sprint. Returns it, or `None` under `dry_run`.
+    pub async fn create_sprint(&self, sprint: &NewSprint) -> Result<Option<Sprint>> {
+        let mut body = Map::new();
+        body.insert("name".to_string(), json!(sprint.name));
+        body.insert("originBoardId".to_string(), json!(sprint.board_id));
+        if let Some(start) = &sprint.start_date {
+            body.insert("startDate".to_string(), json!(start));
+        }
+        if let Some(end) = &sprint.end_date {
+            body.insert("endDate".to_string(), json!(end));
+        }
+        if let Some(goal) = &sprint.goal {
+            body.insert("goal".to_string(), json!(goal));
+        }
+        self.write_sprint(
+            Request::post("sprint", Value::Object(body))
+                .agile()
+                .action("create sprint"),
+        )
+        .await
+    }
+
+    /// Partially updates a sprint; only the given keys change. Returns the
+    /// updated sprint, or `None` under `dry_run`.
+    pub async fn update_sprint(
+        &self,
+        sprint_id: u64,
+        changes: Map<String, Value>,
+    ) -> Result<Option<Sprint>> {
+        self.write_sprint(
+            Request::post(format!("sprint/{sprint_id}"), Value::Object(changes))
+                .agile()
+                .action("update sprint"),
+        )
+        .await
+    }
+
+    /// Starts a future sprint. Jira needs both dates, so pass any the sprint does not have yet.
+    pub async fn start_sprint(
+        &self,
+        sprint_id: u64,
+        start_date: Option<String>,
+        end_date: Option<String>,
+    ) -> Result<Option<Sprint>> {
+        let mut changes = Map::new();
+        changes.insert("state".to_string(), json!("active"));
+        if let Some(start) = start_date {
+            changes.insert("startDate".to_string(), json!(start));
+        }
+        if let Some(end) = end_date {
+            changes.insert("endDate".to_string(), json!(end));
+        }
+        self.update_sprint(sprint_id, changes).await
+    }
+
+    /// Moves every issue that is not done out of an active sprint, then closes it.
+    /// Sub-tasks are skipped because they follow their parents.
+    pub async fn close_sprint_with_carry_over(
+        &self,
+        sprint_id: u64,
+        carry_over: CarryOver,
+    ) -> Result<SprintCloseReport> {
+        let sprint = self.get_sprint(sprint_id).await?;
+        let issues = self.sprint_issues(sprint_id).await?;
+        let (completed, open): (Vec<&Issue>, Vec<&Issue>) =
+            issues.iter().partition(|issue| issue.is_done());
+        let moved: Vec<String> = open
+            .iter()
+            .filter(|issue| !issue.fields.issuetype.as_ref().is_some_and(|t| t.subtask))
+            .map(|issue| issue.key.clone())
+            .collect();
+
+        let moved_to = match carry_over {
+            CarryOver::Next => {
+                let board_id = sprint.origin_board_id.ok_or_else(|| {
+                    JiraError::new(
+                        ErrorKind::Validation,
+                        format!("Sprint {sprint_id} has no board; use --move-open-to backlog"),
+                    )
+                })?;
+                let next = self
+                    .list_sprints(board_id, &["future".to_string()])
+                    .await?
+                    .into_iter()
+                    .find(|candidate| candidate.id != sprint_id)
+                    .ok_or_else(|| {
+                        JiraError::new(
+                            ErrorKind::Validation,
+                            format!(
+                                "Board {board_id} has no future sprint to move open issues to; create one or use --move-open-to backlog"
+                            ),
+                        )
+                    })?;
+                if !moved.is_empty() {
+                    self.move_issues_to_sprint(next.id, &moved).await?;
+                }
+                Some(next)
+            }
+            CarryOver::Backlog => {
+                if !moved.is_empty() {
+                    self.move_issues_to_backlog(&moved).await?;
+                }
+                None
+            }
+        };
+
+        let mut changes = Map::new();
+        changes.insert("state".to_string(), json!("closed"));
+        let closed = self.update_sprint(sprint_id, changes).await?;
+        Ok(SprintCloseReport {
+            sprint: closed.unwrap_or(sprint),
+            moved_to,
+            moved,
+            completed: completed.iter().map(|issue| issue.key.clone()).collect(),
+        })
+    }
+
+    /// Moves issues out of any sprint into the board backlog.
+    pub async fn move_issues_to_backlog(&self, keys: &[String]) -> Result<()> {
+        for chunk in keys.chunks(MAX_ISSUES_PER_MOVE) {
+            self.execute(
+                Request::post("backlog/issue", json!({ "issues": chunk }))
+                    .agile()
+                    .idempotent()
+                    .action("move issues to backlog"),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Moves issues into a sprint, in batches the API accepts.
+    pub async fn move_issues_to_sprint(&self, sprint_id: u64, keys: &[String]) -> Result<()> {
+        for chunk in keys.chunks(MAX_ISSUES_PER_MOVE) {
+            // Setting sprint membership is safe to repeat, so allow retries.
+            self.execute(
+                Request::post(
+                    format!("sprint/{sprint_id}/issue"),
+                    json!({ "issues": chunk }),
+                )
+                .agile()
+                .idempotent()
+                .action("move issues to sprint"),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_client;
+    use httpmock::prelude::*;
+
+    #[tokio::test]
+    async fn list_sprints_filters_by_state_and_follows_pages() {
+        let server = MockServer::start();
+        let first = server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/agile/1.0/board/7/sprint")
+                .query_param("state", "active,future")
+                .query_param("startAt", "0");
+            then.status(200).json_body(json!({
+                "isLast": false,
+                "values": [{ "id": 1, "name": "Sprint 1", "state": "active" }]
+            }));
+        });
+        let second = server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/agile/1.0/board/7/sprint")
+                .query_param("startAt", "1");
+            then.status(200).json_body(json!({
+                "isLast": true,
+                "values": [{ "id": 2, "name": "Sprint 2", "state": "future" }]
+            }));
+        });
+
+        let sprints = test_client(&server)
+            .list_sprints(7, &["active".to_string(), "future".to_string()])
+            .await
+            .unwrap();
+
+        first.assert();
+        second.assert();
+        let names: Vec<_> = sprints.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["Sprint 1", "Sprint 2"]);
+    }
+
+    #[tokio::test]
+    async fn sprint_issues_pages_by_total() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/agile/1.0/sprint/5/issue")
+                .query_param("fields", SPRINT_ISSUE_FIELDS);
+            then.status(200).json_body(json!({
+                "startAt": 0,
+                "total": 1,
+                "issues": [{ "id": "1", "key": "ACME-1", "fields": { "summary": "Fix" } }]
+            }));
+        });
+
+        let issues = test_client(&server).sprint_issues(5).await.unwrap();
+
+        mock.assert_hits(1);
+        assert_eq!(issues[0].key, "ACME-1");
+    }
+
+    #[tokio::test]
+    async fn close_sprint_moves_open_issues_to_next_sprint() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/agile/1.0/sprint/5");
+            then.status(200).json_body(json!({
+                "id": 5, "name": "Sprint 5", "state": "active", "originBoardId": 7
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/agile/1.0/sprint/5/issue");
+            then.status(200).json_body(json!({
+                "total": 3,
+                "issues": [
+                    { "key": "ACME-1", "fields": { "status": { "name": "Done", "statusCategory": { "key": "done" } } } },
+                    { "key": "ACME-2", "fields": { "status": { "name": "In Progress", "statusCategory": { "key": "indeterminate" } } } },
+                    { "key": "ACME-3", "fields": {
+                        "status": { "name": "To Do", "statusCategory": { "key": "new" } },
+                        "issuetype": { "name": "Sub-task", "subtask": true } } }
+                ]
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/agile/1.0/board/7/sprint")
+                .query_param("state", "future");
+            then.status(200).json_body(json!({
+                "isLast": true,
+                "values": [{ "id": 6, "name": "Sprint 6", "state": "future" }]
+            }));
+        });
+        let moved = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/agile/1.0/sprint/6/issue")
+                .json_body(json!({ "issues": ["ACME-2"] }));
+            then.status(204);
+        });
+        let closed = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/agile/1.0/sprint/5")
+                .json_body(json!({ "state": "closed" }));
+            then.status(200)
+                .json_body(json!({ "id": 5, "name": "Sprint 5", "state": "closed" }));
+        });
+
+        let report = test_client(&server)
+            .close_sprint_with_carry_over(5, CarryOver::Next)
+            .await
+            .unwrap();
+
+        moved.assert();
+        closed.assert();
+        assert_eq!(report.moved, ["ACME-2"]);
+        assert_eq!(report.completed, ["ACME-1"]);
+        assert_eq!(report.moved_to.unwrap().id, 6);
+        assert_eq!(report.sprint.state, "closed");
+    }
+
+    #[tokio::test]
+    async fn close_sprint_without_future_sprint_fails_before_changing_anything() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/agile/1.0/sprint/5");
+            then.status(200)
+                .json_body(json!({ "id": 5, "state": "active", "originBoardId": 7 }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/agile/1.0/sprint/5/issue");
+            then.status(200)
+                .json_body(json!({ "total": 0, "issues": [] }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/agile/1.0/board/7/sprint");
+            then.status(200)
+                .json_body(json!({ "isLast": true, "values": [] }));
+        });
+        let closed = server.mock(|when, then| {
+            when.method(POST).path("/rest/agile/1.0/sprint/5");
+            then.status(200).json_body(json!({ "id": 5 }));
+        });
+
+        let err = test_client(&server)
+            .close_sprint_with_carry_over(5, CarryOver::Next)
+            .await
+            .unwrap_err();
+
+        closed.assert_hits(0);
+        assert!(err.to_string().contains("no future sprint"));
+    }
+
+    #[tokio::test]
+    async fn move_issues_to_sprint_batches_by_fifty() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/rest/agile/1.0/sprint/5/issue");
+            then.status(204);
+        });
+        let keys: Vec<String> = (1..=120).map(|n| format!("ACME-{n}")).collect();
+
+        test_client(&server)
+            .move_issues_to_sprint(5, &keys)
+            .await
+            .unwrap();
+
+        mock.assert_hits(3);
+    }
+}
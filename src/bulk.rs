@@ -0,0 +1,165 @@
+//! One operation applied to many issues, a few at a time.
+
+use anyhow::Result;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use crate::client::{JiraClient, Request};
+
+/// Fields fetched to preview the issues a bulk run will touch.
+pub const BULK_PREVIEW_FIELDS: [&str; 2] = ["summary", "status"];
+
+/// Requests in flight when no concurrency is given.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum BulkOperation {
+    /// Run the transition with this name (case-insensitive).
+    Transition { to: String },
+    /// Set these fields.
+    Update { fields: Map<String, Value> },
+    /// Assign to this account id, or unassign with `None`.
+    Assign { account_id: Option<String> },
+    /// Add and remove labels, keeping the others.
+    Label {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+}
+
+/// The result for one issue; `error` is unset on success.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkOutcome {
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl JiraClient {
+    pub async fn apply(&self, key: &str, operation: &BulkOperation) -> Result<()> {
+        match operation {
+            BulkOperation::Transition { to } => {
+                self.transition_issue(key, to).await?;
+            }
+            BulkOperation::Update { fields } => {
+                self.update_issue(key, fields.clone()).await?;
+            }
+            BulkOperation::Assign { account_id } => {
+                let before = self.snapshot_fields(key, &["assignee"]).await?;
+                self.execute(
+                    Request::put(
+                        format!("issue/{key}/assignee"),
+                        json!({ "accountId": account_id }),
+                    )
+                    .action("assign issue"),
+                )
+                .await?;
+                self.record(before)?;
+            }
+            BulkOperation::Label { add, remove } => {
+                let changes: Vec<Value> = add
+                    .iter()
+                    .map(|label| json!({ "add": label }))
+                    .chain(remove.iter().map(|label| json!({ "remove": label })))
+                    .collect();
+                let before = self.snapshot_fields(key, &["labels"]).await?;
+                self.execute(
+                    Request::put(
+                        format!("issue/{key}"),
+                        json!({ "update": { "labels": changes } }),
+                    )
+                    .action("label issue"),
+                )
+                .await?;
+                self.record(before)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies `operation` to every key with at most `concurrency` requests in
+    /// flight; `on_done` sees each outcome as it lands. Outcomes come back in `keys` order.
+    pub async fn apply_all(
+        &self,
+        keys: &[String],
+        operation: &BulkOperation,
+        concurrency: usize,
+        mut on_done: impl FnMut(&BulkOutcome),
+    ) -> Vec<BulkOutcome> {
+        let mut results = stream::iter(keys.iter().enumerate())
+            .map(|(index, key)| async move {
+                let error = self
+                    .apply(key, operation)
+                    .await
+                    .err()
+                    .map(|err| format!("{err:#}"));
+                (
+                    index,
+                    BulkOutcome {
+                        key: key.clone(),
+                        error,
+                    },
+                )
+            })
+            .buffer_unordered(concurrency.max(1));
+        let mut outcomes = Vec::with_capacity(keys.len());
+        while let Some((index, outcome)) = results.next().await {
+            on_done(&outcome);
+            outcomes.push((index, outcome));
+        }
+        outcomes.sort_by_key(|(index, _)| *index);
+        outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_client;
+    use httpmock::prelude::*;
+
+    #[tokio::test]
+    async fn apply_all_labels_every_issue_and_keeps_key_order() {
+        let server = MockServer::start();
+        let labeled = server.mock(|when, then| {
+            when.method(PUT)
+                .path_matches(Regex::new("^/rest/api/3/issue/ACME-[12]$").unwrap())
+                .json_body(
+                    json!({ "update": { "labels": [{ "add": "flaky" }, { "remove": "new" }] } }),
+                );
+            then.status(204);
+        });
+        let missing = server.mock(|when, then| {
+            when.method(PUT).path("/rest/api/3/issue/ACME-3");
+            then.status(404)
+                .json_body(json!({ "errorMessages": ["Issue does not exist"] }));
+        });
+        let keys: Vec<String> = ["ACME-1", "ACME-2", "ACME-3"].map(String::from).to_vec();
+        let operation = BulkOperation::Label {
+            add: vec!["flaky".to_string()],
+            remove: vec!["new".to_string()],
+        };
+
+        let mut seen = 0;
+        let outcomes = test_client(&server)
+            .apply_all(&keys, &operation, 2, |_| seen += 1)
+            .await;
+
+        labeled.assert_hits(2);
+        missing.assert();
+        assert_eq!(seen, 3);
+        let keys: Vec<&str> = outcomes.iter().map(|o| o.key.as_str()).collect();
+        assert_eq!(keys, ["ACME-1", "ACME-2", "ACME-3"]);
+        assert!(outcomes[0].error.is_none());
+        assert!(
+            outcomes[2]
+                .error
+                .as_deref()
+                .unwrap()
+                .contains("Issue does not exist")
+        );
+    }
+}
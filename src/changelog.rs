@@ -0,0 +1,284 @@
+//! An issue's change history: who changed which field, when, from what to what.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::client::{JiraClient, Request};
+use crate::models::User;
+
+/// Change sets per changelog page (Jira's maximum).
+const CHANGELOG_PAGE_SIZE: u32 = 100;
+
+/// Changelogs fetched at once when reports need one per issue.
+const CHANGELOG_CONCURRENCY: usize = 8;
+
+/// One field change within a [`ChangeSet`]. `from`/`to` hold ids (status id,
+/// account id, ...), `from_text`/`to_text` the readable values.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeItem {
+    #[serde(default)]
+    pub field: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(
+        default,
+        rename = "fromString",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub from_text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    #[serde(default, rename = "toString", skip_serializing_if = "Option::is_none")]
+    pub to_text: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ChangeItem {
+    /// Matches `name` against the field name or id, case-insensitively.
+    pub fn is_field(&self, name: &str) -> bool {
+        self.field.eq_ignore_ascii_case(name)
+            || self
+                .field_id
+                .as_deref()
+                .is_some_and(|id| id.eq_ignore_ascii_case(name))
+    }
+}
+
+/// The changes one user made at one moment.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChangeSet {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<User>,
+    #[serde(default)]
+    pub created: String,
+    #[serde(default)]
+    pub items: Vec<ChangeItem>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ChangeSet {
+    pub fn at(&self) -> Option<DateTime<FixedOffset>> {
+        parse_jira_time(&self.created)
+    }
+}
+
+/// One field change on the timeline, with its change set's time and author.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineEvent {
+    pub at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    pub field: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// A status an issue sat in from `since` until `until` (`None`: it still does).
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusPeriod {
+    pub status: String,
+    pub since: DateTime<FixedOffset>,
+    pub until: Option<DateTime<FixedOffset>>,
+}
+
+/// Replays the status changes of an issue created at `created` and now in
+/// status `current`. The first status is the one the first change left, or
+/// `current` when the status never changed.
+pub fn status_periods(
+    created: DateTime<FixedOffset>,
+    current: &str,
+    changes: &[ChangeSet],
+) -> Vec<StatusPeriod> {
+    let moves: Vec<(DateTime<FixedOffset>, &ChangeItem)> = changes
+        .iter()
+        .filter_map(|set| Some((set.at()?, set)))
+        .flat_map(|(at, set)| {
+            set.items
+                .iter()
+                .filter(|item| item.is_field("status"))
+                .map(move |item| (at, item))
+        })
+        .collect();
+    let first = moves
+        .first()
+        .and_then(|(_, item)| item.from_text.as_deref())
+        .unwrap_or(current);
+    let mut periods = vec![StatusPeriod {
+        status: first.to_string(),
+        since: created,
+        until: None,
+    }];
+    for (at, item) in moves {
+        if let Some(open) = periods.last_mut() {
+            open.until = Some(at);
+        }
+        periods.push(StatusPeriod {
+            status: item.to_text.clone().unwrap_or_default(),
+            since: at,
+            until: None,
+        });
+    }
+    periods
+}
+
+/// The first change to `field` (name or id) after `at`. Its `from` side is the
+/// value the field had at `at`; with no later change, the current value holds.
+pub fn next_change<'a>(
+    changes: &'a [ChangeSet],
+    field: &str,
+    at: DateTime<FixedOffset>,
+) -> Option<&'a ChangeItem> {
+    changes
+        .iter()
+        .filter(|set| set.at().is_some_and(|when| when > at))
+        .flat_map(|set| &set.items)
+        .find(|item| item.is_field(field))
+}
+
+/// Parses Jira timestamps such as `2026-10-01T09:30:00.000+0000`, and RFC 3339.
+pub fn parse_jira_time(value: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z")
+        .or_else(|_| DateTime::parse_from_rfc3339(value))
+        .ok()
+}
+
+/// Flattens change sets into one event per field change, oldest first,
+/// keeping only `fields` (by name or id) when any are given.
+pub fn timeline(changes: &[ChangeSet], fields: &[String]) -> Vec<TimelineEvent> {
+    let mut sets: Vec<&ChangeSet> = changes.iter().collect();
+    sets.sort_by_key(|set| set.at());
+    sets.into_iter()
+        .flat_map(|set| {
+            set.items
+                .iter()
+                .filter(|item| fields.is_empty() || fields.iter().any(|f| item.is_field(f)))
+                .map(move |item| TimelineEvent {
+                    at: set.created.clone(),
+                    author: set.author.as_ref().map(|a| a.display_name.clone()),
+                    field: item.field.clone(),
+                    from: item.from_text.clone().or_else(|| item.from.clone()),
+                    to: item.to_text.clone().or_else(|| item.to.clone()),
+                })
+        })
+        .collect()
+}
+
+impl JiraClient {
+    /// Every change set of `key`, oldest first.
+    pub async fn changelog(&self, key: &str) -> Result<Vec<ChangeSet>> {
+        let mut changes: Vec<ChangeSet> = Vec::new();
+        loop {
+            let page = self
+                .execute(
+                    Request::get(format!("issue/{key}/changelog"))
+                        .query("startAt", changes.len())
+                        .query("maxResults", CHANGELOG_PAGE_SIZE)
+                        .action("get changelog"),
+                )
+                .await?;
+            let values: Vec<ChangeSet> =
+                serde_json::from_value(page.get("values").cloned().unwrap_or_default())
+                    .context("Failed to parse get changelog response")?;
+            let fetched = values.len();
+            changes.extend(values);
+            let total = page["total"].as_u64().unwrap_or(0) as usize;
+            if fetched == 0 || page["isLast"].as_bool() == Some(true) || changes.len() >= total {
+                break;
+            }
+        }
+        changes.sort_by_key(ChangeSet::at);
+        Ok(changes)
+    }
+
+    /// The changelog of each of `keys`, in the same order.
+    pub async fn changelogs(&self, keys: &[String]) -> Result<Vec<Vec<ChangeSet>>> {
+        stream::iter(keys)
+            .map(|key| self.changelog(key))
+            .buffered(CHANGELOG_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_client;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn changelog_follows_pages_and_timeline_filters_fields() {
+        let server = MockServer::start();
+        let first = server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/api/3/issue/ACME-1/changelog")
+                .query_param("startAt", "0");
+            then.status(200).json_body(json!({
+                "startAt": 0, "total": 2, "isLast": false,
+                "values": [{
+                    "id": "1",
+                    "author": { "accountId": "a1", "displayName": "Ada" },
+                    "created": "2026-10-01T09:30:00.000+0000",
+                    "items": [
+                        { "field": "status", "fieldId": "status", "from": "1", "fromString": "To Do",
+                          "to": "3", "toString": "In Progress" },
+                        { "field": "assignee", "fieldId": "assignee", "to": "a1", "toString": "Ada" }
+                    ]
+                }]
+            }));
+        });
+        let second = server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/api/3/issue/ACME-1/changelog")
+                .query_param("startAt", "1");
+            then.status(200).json_body(json!({
+                "startAt": 1, "total": 2, "isLast": true,
+                "values": [{
+                    "id": "2",
+                    "created": "2026-10-01T08:00:00.000-0300",
+                    "items": [{ "field": "status", "fromString": "In Progress", "toString": "Done" }]
+                }]
+            }));
+        });
+        let client = test_client(&server);
+
+        let changes = client.changelog("ACME-1").await.unwrap();
+
+        first.assert();
+        second.assert();
+        // 08:00 at -03:00 is 11:00 UTC, after the first change set.
+        let events = timeline(&changes, &["Status".to_string()]);
+        let steps: Vec<(Option<&str>, Option<&str>)> = events
+            .iter()
+            .map(|e| (e.from.as_deref(), e.to.as_deref()))
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                (Some("To Do"), Some("In Progress")),
+                (Some("In Progress"), Some("Done"))
+            ]
+        );
+        assert_eq!(events[0].author.as_deref(), Some("Ada"));
+        assert_eq!(timeline(&changes, &[]).len(), 3);
+
+        let created = parse_jira_time("2026-09-30T12:00:00.000+0000").unwrap();
+        let periods = status_periods(created, "Done", &changes);
+        let statuses: Vec<&str> = periods.iter().map(|p| p.status.as_str()).collect();
+        assert_eq!(statuses, ["To Do", "In Progress", "Done"]);
+        assert_eq!(periods[0].until, changes[0].at());
+        assert_eq!(periods[2].until, None);
+    }
+}
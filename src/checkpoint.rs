@@ -0,0 +1,195 @@
+//! Progress files that let an interrupted `bulk` or `import` run pick up
+//! where it stopped instead of starting over.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::bulk::{BulkOperation, BulkOutcome};
+use crate::error::{ErrorKind, JiraError};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Checkpoint {
+    Bulk(BulkCheckpoint),
+    Import(ImportCheckpoint),
+}
+
+/// A bulk run: the issues matched when it started and what happened to each.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkCheckpoint {
+    pub jql: String,
+    pub operation: BulkOperation,
+    /// Matched issues; kept so a resumed run does not depend on the query,
+    /// whose results the run itself may have changed.
+    pub keys: Vec<String>,
+    #[serde(default)]
+    pub done: BTreeSet<String>,
+    /// Error message by issue key.
+    #[serde(default)]
+    pub failed: BTreeMap<String, String>,
+}
+
+/// An import run, with outcomes by 1-based row number.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportCheckpoint {
+    pub file: PathBuf,
+    /// `csv`, `json` or `yaml`.
+    pub format: String,
+    #[serde(default)]
+    pub map: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Rows in the file, to notice when it changed between runs.
+    pub rows: usize,
+    /// Created issue key by row.
+    #[serde(default)]
+    pub created: BTreeMap<usize, String>,
+    /// Error message by row.
+    #[serde(default)]
+    pub failed: BTreeMap<usize, String>,
+}
+
+impl Checkpoint {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read checkpoint {}", path.display()))?;
+        serde_json::from_str(&text).map_err(|err| {
+            JiraError::new(
+                ErrorKind::Validation,
+                format!("{} is not a jico checkpoint: {err}", path.display()),
+            )
+            .into()
+        })
+    }
+}
+
+/// Writes to a temporary file first so a crash never leaves half a checkpoint.
+fn save(path: &Path, kind: &str, checkpoint: impl Serialize) -> Result<()> {
+    let mut value = serde_json::to_value(checkpoint)?;
+    value["kind"] = kind.into();
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+    std::fs::write(&partial, serde_json::to_string_pretty(&value)?)
+        .with_context(|| format!("Failed to write checkpoint {}", partial.display()))?;
+    std::fs::rename(&partial, path)
+        .with_context(|| format!("Failed to write checkpoint {}", path.display()))
+}
+
+impl BulkCheckpoint {
+    pub fn new(jql: &str, operation: BulkOperation, keys: Vec<String>) -> Self {
+        Self {
+            jql: jql.to_string(),
+            operation,
+            keys,
+            done: BTreeSet::new(),
+            failed: BTreeMap::new(),
+        }
+    }
+
+    /// Keys neither done nor failed yet, in match order.
+    pub fn pending(&self) -> Vec<String> {
+        self.keys
+            .iter()
+            .filter(|key| !self.done.contains(*key) && !self.failed.contains_key(*key))
+            .cloned()
+            .collect()
+    }
+
+    pub fn record(&mut self, outcome: &BulkOutcome) {
+        match &outcome.error {
+            None => {
+                self.failed.remove(&outcome.key);
+                self.done.insert(outcome.key.clone());
+            }
+            Some(error) => {
+                self.failed.insert(outcome.key.clone(), error.clone());
+            }
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.done.len() == self.keys.len()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        save(path, "bulk", self)
+    }
+}
+
+impl ImportCheckpoint {
+    pub fn record(&mut self, row: usize, outcome: &Result<String, String>) {
+        match outcome {
+            Ok(key) => {
+                self.failed.remove(&row);
+                self.created.insert(row, key.clone());
+            }
+            Err(error) => {
+                self.failed.insert(row, error.clone());
+            }
+        }
+    }
+
+    /// Outcomes to carry into the next run. Resuming sends only the rows not
+    /// tried yet; with `retry_failed`, only the rows that failed are sent.
+    pub fn previous(&self, retry_failed: bool) -> BTreeMap<usize, Result<String, String>> {
+        (1..=self.rows)
+            .filter_map(
+                |row| match (self.created.get(&row), self.failed.get(&row)) {
+                    (Some(key), _) => Some((row, Ok(key.clone()))),
+                    (None, Some(error)) if !retry_failed => Some((row, Err(error.clone()))),
+                    (None, Some(_)) => None,
+                    (None, None) if retry_failed => Some((
+                        row,
+                        Err("not tried yet; resume without --retry-failed".to_string()),
+                    )),
+                    (None, None) => None,
+                },
+            )
+            .collect()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.created.len() == self.rows
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        save(path, "import", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bulk_checkpoint_round_trips_and_tracks_pending_keys() {
+        let keys = ["ACME-1", "ACME-2", "ACME-3"].map(String::from).to_vec();
+        let operation = BulkOperation::Transition {
+            to: "Closed".to_string(),
+        };
+        let mut checkpoint = BulkCheckpoint::new("labels = flaky", operation, keys);
+        checkpoint.record(&BulkOutcome {
+            key: "ACME-1".to_string(),
+            error: None,
+        });
+        checkpoint.record(&BulkOutcome {
+            key: "ACME-2".to_string(),
+            error: Some("no such transition".to_string()),
+        });
+        assert_eq!(checkpoint.pending(), ["ACME-3"]);
+
+        let path =
+            std::env::temp_dir().join(format!("jico-checkpoint-{}.json", std::process::id()));
+        checkpoint.save(&path).unwrap();
+        let loaded = Checkpoint::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, Checkpoint::Bulk(checkpoint));
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+use clap::Subcommand;
+use jico::{CarryOver, ErrorKind, Issue, JiraClient, JiraError, NewSprint};
+use serde_json::{Value, json};
+
+use super::print_json;
+
+#[derive(Subcommand, Debug)]
+pub enum BoardCommand {
+    /// List boards
+    List {
+        /// Only boards of this project (key or id)
+        #[arg(long)]
+        project: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SprintCommand {
+    /// List sprints of a board
+    List {
+        /// Board id
+        #[arg(long)]
+        board: u64,
+        /// Sprint states to include (comma-separated or repeated)
+        #[arg(long, value_delimiter = ',', value_parser = ["future", "active", "closed"])]
+        state: Vec<String>,
+    },
+    /// Show a sprint with its issues grouped by status
+    View {
+        /// Sprint id
+        id: u64,
+    },
+    /// Create a future sprint on a board
+    Create {
+        /// Board id
+        #[arg(long)]
+        board: u64,
+        /// Sprint name
+        #[arg(long)]
+        name: String,
+        /// Start date (YYYY-MM-DD or RFC 3339)
+        #[arg(long)]
+        start: Option<String>,
+        /// End date (YYYY-MM-DD or RFC 3339)
+        #[arg(long)]
+        end: Option<String>,
+        /// Sprint goal
+        #[arg(long)]
+        goal: Option<String>,
+    },
+    /// Start a future sprint
+    Start {
+        /// Sprint id
+        id: u64,
+        /// Start date (YYYY-MM-DD or RFC 3339); default: the sprint's own, else now
+        #[arg(long)]
+        start: Option<String>,
+        /// End date (YYYY-MM-DD or RFC 3339); required when the sprint has none
+        #[arg(long)]
+        end: Option<String>,
+    },
+    /// Close an active sprint, first moving its unfinished issues
+    Close {
+        /// Sprint id
+        id: u64,
+        /// Where unfinished issues go
+        #[arg(long, value_enum)]
+        move_open_to: CarryOver,
+    },
+    /// Move issues into a sprint
+    Add {
+        /// Sprint id
+        sprint: u64,
+        /// Issue keys, e.g., PROJ-1 PROJ-2
+        #[arg(required = true)]
+        keys: Vec<String>,
+    },
+}
+
+pub async fn run_board(client: &JiraClient, command: BoardCommand) -> Result<()> {
+    match command {
+        BoardCommand::List { project } => {
+            let boards = client.list_boards(project.as_deref()).await?;
+            print_json(&boards);
+        }
+    }
+    Ok(())
+}
+
+pub async fn run_sprint(client: &JiraClient, command: SprintCommand) -> Result<()> {
+    match command {
+        SprintCommand::List { board, state } => {
+            let sprints = client.list_sprints(board, &state).await?;
+            print_json(&sprints);
+        }
+        SprintCommand::View { id } => {
+            let sprint = client.get_sprint(id).await?;
+            let issues = client.sprint_issues(id).await?;
+            print_json(&json!({
+                "sprint": sprint,
+                "statuses": group_by_status(&issues),
+            }));
+        }
+        SprintCommand::Create {
+            board,
+            name,
+            start,
+            end,
+            goal,
+        } => {
+            let sprint = NewSprint {
+                board_id: board,
+                name,
+                start_date: start.as_deref().map(sprint_date).transpose()?,
+                end_date: end.as_deref().map(sprint_date).transpose()?,
+                goal,
+            };
+            let created = client.create_sprint(&sprint).await?;
+            print_json(&created);
+        }
+        SprintCommand::Start { id, start, end } => {
+            let sprint = client.get_sprint(id).await?;
+            let start = match start {
+                Some(start) => Some(sprint_date(&start)?),
+                None if sprint.start_date.is_none() => Some(Local::now().to_rfc3339()),
+                None => None,
+            };
+            let end = match end {
+                Some(end) => Some(sprint_date(&end)?),
+                None if sprint.end_date.is_none() => {
+                    return Err(JiraError::new(
+                        ErrorKind::Validation,
+                        format!("Sprint {id} has no end date; pass --end"),
+                    )
+                    .into());
+                }
+                None => None,
+            };
+            let started = client.start_sprint(id, start, end).await?;
+            print_json(&started);
+        }
+        SprintCommand::Close { id, move_open_to } => {
+            let report = client
+                .close_sprint_with_carry_over(id, move_open_to)
+                .await?;
+            print_json(&report);
+        }
+        SprintCommand::Add { sprint, keys } => {
+            client.move_issues_to_sprint(sprint, &keys).await?;
+            // A dry run moves nothing; only report the keys as planned.
+            let outcome = if client.dry_run { "planned" } else { "added" };
+            print_json(&json!({ "sprint": sprint, outcome: keys }));
+        }
+    }
+    Ok(())
+}
+
+/// Groups issues by status, ordered To Do → In Progress → Done and then by status name.
+fn group_by_status(issues: &[Issue]) -> Vec<Value> {
+    let mut groups: BTreeMap<(u8, String), Vec<Value>> = BTreeMap::new();
+    for issue in issues {
+        let rank = match issue.status_category() {
+            Some("new") => 0,
+            Some("indeterminate") => 1,
+            Some("done") => 2,
+            _ => 3,
+        };
+        let status = issue.status_name().unwrap_or("Unknown").to_string();
+        groups.entry((rank, status)).or_default().push(json!({
+            "key": issue.key,
+            "summary": issue.fields.summary,
+            "assignee": issue.fields.assignee.as_ref().map(|a| &a.display_name),
+        }));
+    }
+    groups
+        .into_iter()
+        .map(|((_, status), issues)| {
+            json!({ "status": status, "count": issues.len(), "issues": issues })
+        })
+        .collect()
+}
+
+/// Accepts `YYYY-MM-DD` (local midnight) or an RFC 3339 timestamp and returns RFC 3339.
+fn sprint_date(value: &str) -> Result<String> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.to_rfc3339());
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| anyhow!("Invalid date '{value}' (expected YYYY-MM-DD or RFC 3339)"))?;
+    let midnight = date.and_hms_opt(0, 0, 0).expect("midnight is a valid time");
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|local| local.to_rfc3339())
+        .ok_or_else(|| anyhow!("Date '{value}' does not exist in the local time zone"))
+}
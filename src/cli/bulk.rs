@@ -0,0 +1,260 @@
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use chrono::Local;
+use clap::{Args, Subcommand};
+use jico::bulk::{BULK_PREVIEW_FIELDS, DEFAULT_CONCURRENCY};
+use jico::{BulkCheckpoint, BulkOperation, Checkpoint, ErrorKind, Issue, JiraClient, JiraError};
+use serde_json::{Map, Value, json};
+
+use super::print_json;
+
+/// Issues listed before asking for confirmation.
+const PREVIEW_ROWS: usize = 10;
+
+#[derive(Subcommand, Debug)]
+pub enum BulkCommand {
+    /// Transition every matching issue
+    Transition {
+        /// Target status/transition name
+        #[arg(long)]
+        to: String,
+    },
+    /// Set fields on every matching issue
+    Update {
+        /// Priority name
+        #[arg(long)]
+        priority: Option<String>,
+        /// Labels replacing the current ones (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        labels: Option<Vec<String>>,
+        /// Any field as FIELD=VALUE; VALUE is parsed as JSON when it is valid JSON
+        #[arg(long = "set", value_name = "FIELD=VALUE")]
+        set: Vec<String>,
+    },
+    /// Assign every matching issue
+    Assign {
+        /// Account id, or `none` to unassign
+        #[arg(long)]
+        to: String,
+    },
+    /// Add and remove labels on every matching issue, keeping the others
+    Label {
+        /// Labels to add (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        add: Vec<String>,
+        /// Labels to remove (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        remove: Vec<String>,
+    },
+}
+
+impl BulkCommand {
+    fn into_operation(self) -> Result<BulkOperation> {
+        Ok(match self {
+            BulkCommand::Transition { to } => BulkOperation::Transition { to },
+            BulkCommand::Update {
+                priority,
+                labels,
+                set,
+            } => {
+                let mut fields = Map::new();
+                if let Some(priority) = priority {
+                    fields.insert("priority".to_string(), json!({ "name": priority }));
+                }
+                if let Some(labels) = labels {
+                    fields.insert("labels".to_string(), json!(labels));
+                }
+                for assignment in set {
+                    let (field, value) = assignment.split_once('=').ok_or_else(|| {
+                        anyhow!("Invalid --set '{assignment}' (expected FIELD=VALUE)")
+                    })?;
+                    fields.insert(field.to_string(), field_value(value));
+                }
+                if fields.is_empty() {
+                    return Err(anyhow!(
+                        "Provide at least one field to update (--priority, --labels, --set)"
+                    ));
+                }
+                BulkOperation::Update { fields }
+            }
+            BulkCommand::Assign { to } => BulkOperation::Assign {
+                account_id: (!to.eq_ignore_ascii_case("none")).then_some(to),
+            },
+            BulkCommand::Label { add, remove } => {
+                if add.is_empty() && remove.is_empty() {
+                    return Err(anyhow!("Provide --add and/or --remove"));
+                }
+                BulkOperation::Label { add, remove }
+            }
+        })
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct BulkArgs {
+    /// Issues to change
+    #[arg(long, required_unless_present = "resume")]
+    jql: Option<String>,
+    /// Skip the confirmation prompt
+    #[arg(long)]
+    yes: bool,
+    /// Issues changed at the same time
+    #[arg(long, default_value_t = DEFAULT_CONCURRENCY)]
+    concurrency: usize,
+    /// Where to record progress; default: jico-bulk-<timestamp>.checkpoint.json
+    #[arg(long, conflicts_with = "resume")]
+    checkpoint: Option<PathBuf>,
+    /// Continue the run recorded in this checkpoint file
+    #[arg(long, conflicts_with = "jql")]
+    resume: Option<PathBuf>,
+    /// With --resume, retry only the issues that failed
+    #[arg(long, requires = "resume")]
+    retry_failed: bool,
+    #[command(subcommand)]
+    command: Option<BulkCommand>,
+}
+
+pub async fn run_bulk(client: &JiraClient, args: BulkArgs) -> Result<()> {
+    let (mut checkpoint, path, keys) = match args.resume {
+        Some(path) => {
+            let Checkpoint::Bulk(checkpoint) = Checkpoint::load(&path)? else {
+                return Err(JiraError::new(
+                    ErrorKind::Validation,
+                    format!(
+                        "{} belongs to an import; use jico import --resume",
+                        path.display()
+                    ),
+                )
+                .into());
+            };
+            let keys = if args.retry_failed {
+                checkpoint.failed.keys().cloned().collect()
+            } else {
+                checkpoint.pending()
+            };
+            eprintln!(
+                "Resuming {}: {} of {} issue(s) to go.",
+                path.display(),
+                keys.len(),
+                checkpoint.keys.len()
+            );
+            (checkpoint, path, keys)
+        }
+        None => {
+            let jql = args.jql.unwrap_or_default();
+            let command = args.command.ok_or_else(|| {
+                anyhow!("Choose an operation: transition, update, assign or label")
+            })?;
+            let operation = command.into_operation()?;
+            let issues = client.search_all(&jql, &BULK_PREVIEW_FIELDS).await?;
+            preview(&issues);
+            if issues.is_empty() {
+                return Ok(());
+            }
+            let keys: Vec<String> = issues.into_iter().map(|issue| issue.key).collect();
+            let path = args
+                .checkpoint
+                .unwrap_or_else(|| default_checkpoint_path("bulk"));
+            (
+                BulkCheckpoint::new(&jql, operation, keys.clone()),
+                path,
+                keys,
+            )
+        }
+    };
+
+    // A dry run changes nothing, so it needs neither a confirmation nor a checkpoint.
+    let record = !client.dry_run;
+    if !keys.is_empty() {
+        if record && !args.yes && !confirm(&format!("Apply to {} issue(s)? [y/N] ", keys.len()))? {
+            return Err(anyhow!("Aborted; no issues were changed"));
+        }
+        if record {
+            checkpoint.save(&path)?;
+            eprintln!("Recording progress in {}", path.display());
+        }
+        let operation = checkpoint.operation.clone();
+        client
+            .apply_all(&keys, &operation, args.concurrency, |outcome| {
+                checkpoint.record(outcome);
+                if record && let Err(err) = checkpoint.save(&path) {
+                    eprintln!("Warning: {err:#}");
+                }
+            })
+            .await;
+    }
+
+    let failures: Vec<Value> = checkpoint
+        .failed
+        .iter()
+        .map(|(key, error)| json!({ "key": key, "error": error }))
+        .collect();
+    print_json(&json!({
+        "matched": checkpoint.keys.len(),
+        "succeeded": checkpoint.done.len(),
+        "failed": failures.len(),
+        "failures": failures,
+    }));
+    if record {
+        settle_checkpoint(&path, checkpoint.is_complete());
+    }
+    if !failures.is_empty() {
+        return Err(anyhow!("{} issue(s) could not be updated", failures.len()));
+    }
+    Ok(())
+}
+
+fn preview(issues: &[Issue]) {
+    eprintln!("{} issue(s) match.", issues.len());
+    for issue in issues.iter().take(PREVIEW_ROWS) {
+        eprintln!(
+            "  {}  {} ({})",
+            issue.key,
+            issue.fields.summary.as_deref().unwrap_or_default(),
+            issue.status_name().unwrap_or("Unknown")
+        );
+    }
+    if issues.len() > PREVIEW_ROWS {
+        eprintln!("  ... and {} more", issues.len() - PREVIEW_ROWS);
+    }
+}
+
+/// A field value typed on the command line or in a CSV cell: JSON when it
+/// parses (`5`, `true`, `{"id": "3"}`), otherwise the text itself.
+pub(super) fn field_value(text: &str) -> Value {
+    serde_json::from_str(text).unwrap_or_else(|_| json!(text))
+}
+
+/// `jico-<command>-<timestamp>.checkpoint.json` in the current directory.
+pub(super) fn default_checkpoint_path(command: &str) -> PathBuf {
+    let stamp = Local::now().format("%Y%m%d-%H%M%S");
+    PathBuf::from(format!("jico-{command}-{stamp}.checkpoint.json"))
+}
+
+/// Drops the checkpoint once everything is done; otherwise says how to continue.
+pub(super) fn settle_checkpoint(path: &Path, complete: bool) {
+    if complete {
+        if let Err(err) = std::fs::remove_file(path)
+            && err.kind() != io::ErrorKind::NotFound
+        {
+            eprintln!("Warning: failed to remove {}: {err}", path.display());
+        }
+    } else {
+        eprintln!(
+            "Progress kept in {0}; continue with --resume {0} or retry failures with --resume {0} --retry-failed",
+            path.display()
+        );
+    }
+}
+
+/// Asks on stderr and reads one line from stdin; anything but y/yes declines.
+pub(super) fn confirm(prompt: &str) -> Result<bool> {
+    eprint!("{prompt}");
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    let answer = answer.trim();
+    Ok(answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes"))
+}
@@ -0,0 +1,53 @@
+use anyhow::{Result, anyhow};
+use jico::{Issue, JiraClient, Request};
+use serde_json::json;
+
+use super::bulk::confirm;
+use super::print_json;
+
+pub async fn run_delete(
+    client: &JiraClient,
+    key: &str,
+    delete_subtasks: bool,
+    yes: bool,
+) -> Result<()> {
+    let issue: Issue = client
+        .execute_as(
+            Request::get(format!("issue/{key}"))
+                .query("fields", "summary,status,subtasks")
+                .action("get issue"),
+        )
+        .await?;
+    let subtasks: Vec<String> = issue
+        .fields
+        .subtasks
+        .iter()
+        .flatten()
+        .map(|subtask| subtask.key.clone())
+        .collect();
+    eprintln!(
+        "{}  {} ({})",
+        issue.key,
+        issue.fields.summary.as_deref().unwrap_or_default(),
+        issue.status_name().unwrap_or("Unknown")
+    );
+    if !subtasks.is_empty() {
+        let action = if delete_subtasks {
+            "deleted too"
+        } else {
+            "kept; Jira refuses the delete without --delete-subtasks"
+        };
+        eprintln!("  sub-tasks ({action}): {}", subtasks.join(", "));
+    }
+    if !client.dry_run && !yes && !confirm(&format!("Delete {key}? [y/N] "))? {
+        return Err(anyhow!("Aborted; {key} was not deleted"));
+    }
+    client.delete_issue(key, delete_subtasks).await?;
+    let subtasks = if delete_subtasks {
+        subtasks
+    } else {
+        Vec::new()
+    };
+    print_json(&json!({ "deleted": key, "subtasks": subtasks }));
+    Ok(())
+}
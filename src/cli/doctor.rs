@@ -0,0 +1,423 @@
+use std::env;
+
+use anyhow::{Result, anyhow};
+use jico::{JiraClient, Request, Settings};
+use reqwest::StatusCode;
+
+const DOCTOR_PERMISSIONS: [&str; 5] = [
+    "BROWSE_PROJECTS",
+    "CREATE_ISSUES",
+    "EDIT_ISSUES",
+    "TRANSITION_ISSUES",
+    "LINK_ISSUES",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CheckOutcome {
+    Pass,
+    Warn,
+    Fail,
+    Skip,
+}
+
+#[derive(Debug)]
+struct Check {
+    name: String,
+    outcome: CheckOutcome,
+    detail: String,
+    hint: Option<String>,
+}
+
+impl Check {
+    fn new(name: &str, outcome: CheckOutcome, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            outcome,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    fn print(&self) {
+        let tag = match self.outcome {
+            CheckOutcome::Pass => "PASS",
+            CheckOutcome::Warn => "WARN",
+            CheckOutcome::Fail => "FAIL",
+            CheckOutcome::Skip => "SKIP",
+        };
+        println!("[{tag}] {}: {}", self.name, self.detail);
+        if let Some(hint) = &self.hint {
+            println!("       hint: {hint}");
+        }
+    }
+}
+
+pub async fn run_doctor(project: Option<String>) -> Result<()> {
+    let mut checks = Vec::new();
+
+    let base_url = env::var("JIRA_BASE_URL").ok();
+    checks.push(match &base_url {
+        None => Check::new("JIRA_BASE_URL", CheckOutcome::Fail, "not set")
+            .hint("Set JIRA_BASE_URL to your site, e.g. https://acme.atlassian.net"),
+        Some(url) if !url.starts_with("http://") && !url.starts_with("https://") => Check::new(
+            "JIRA_BASE_URL",
+            CheckOutcome::Fail,
+            format!("{url} is not an http(s) URL"),
+        )
+        .hint("Include the scheme, e.g. https://acme.atlassian.net"),
+        Some(url) if url.trim_end_matches('/').ends_with("/rest/api/3") => Check::new(
+            "JIRA_BASE_URL",
+            CheckOutcome::Fail,
+            format!("{url} includes the API path"),
+        )
+        .hint("Use the site root only; jico appends /rest/api/3 itself"),
+        Some(url) => Check::new("JIRA_BASE_URL", CheckOutcome::Pass, url.as_str()),
+    });
+    let email = env::var("JIRA_EMAIL").ok();
+    checks.push(match &email {
+        None => Check::new("JIRA_EMAIL", CheckOutcome::Fail, "not set")
+            .hint("Set JIRA_EMAIL to the email of your Atlassian account"),
+        Some(email) if !email.contains('@') => Check::new(
+            "JIRA_EMAIL",
+            CheckOutcome::Fail,
+            format!("{email} is not an email address"),
+        )
+        .hint("API tokens authenticate with the account email, not the username"),
+        Some(email) => Check::new("JIRA_EMAIL", CheckOutcome::Pass, email.as_str()),
+    });
+    let api_token = env::var("JIRA_API_TOKEN").ok();
+    checks.push(match &api_token {
+        Some(token) if !token.trim().is_empty() => {
+            Check::new("JIRA_API_TOKEN", CheckOutcome::Pass, "set")
+        }
+        _ => Check::new("JIRA_API_TOKEN", CheckOutcome::Fail, "not set")
+            .hint("Create a token at https://id.atlassian.com/manage-profile/security/api-tokens"),
+    });
+    let project = project.or_else(|| env::var("JIRA_PROJECT_KEY").ok());
+    checks.push(match &project {
+        Some(key) => Check::new("JIRA_PROJECT_KEY", CheckOutcome::Pass, key.as_str()),
+        None => Check::new("JIRA_PROJECT_KEY", CheckOutcome::Warn, "not set")
+            .hint("Pass --project or set JIRA_PROJECT_KEY to check project access"),
+    });
+
+    // Loading the settings the way every other command does also catches the
+    // optional variables (JIRA_MAX_ATTEMPTS, JIRA_JOURNAL, ...) it rejects.
+    let settings = if checks.iter().any(|c| c.outcome == CheckOutcome::Fail) {
+        for name in [
+            "JIRA_MAX_ATTEMPTS",
+            "JIRA_JOURNAL",
+            "JIRA_STORY_POINTS_FIELD",
+        ] {
+            checks.push(Check::new(
+                name,
+                CheckOutcome::Skip,
+                "configuration is incomplete",
+            ));
+        }
+        None
+    } else {
+        match Settings::load() {
+            Ok(settings) => {
+                checks.extend(optional_settings_checks(&settings));
+                Some(Settings {
+                    project_key: project.clone(),
+                    ..settings
+                })
+            }
+            Err(err) => {
+                checks.push(
+                    Check::new("Settings", CheckOutcome::Fail, format!("{err:#}"))
+                        .hint("Fix or unset the variable named above"),
+                );
+                None
+            }
+        }
+    };
+    match settings {
+        Some(settings) => {
+            let client = JiraClient::new(&settings)?;
+            doctor_remote_checks(&client, &settings, project.as_deref(), &mut checks).await;
+        }
+        None => {
+            for name in [
+                "Connectivity",
+                "TLS",
+                "Authentication",
+                "Project",
+                "Permissions",
+            ] {
+                checks.push(Check::new(
+                    name,
+                    CheckOutcome::Skip,
+                    "configuration is incomplete",
+                ));
+            }
+        }
+    }
+
+    for check in &checks {
+        check.print();
+    }
+    let failed = checks
+        .iter()
+        .filter(|c| c.outcome == CheckOutcome::Fail)
+        .count();
+    if failed > 0 {
+        return Err(anyhow!("{failed} check(s) failed"));
+    }
+    Ok(())
+}
+
+/// What the optional variables resolved to.
+fn optional_settings_checks(settings: &Settings) -> [Check; 3] {
+    [
+        Check::new(
+            "JIRA_MAX_ATTEMPTS",
+            CheckOutcome::Pass,
+            format!("{} attempt(s) per request", settings.max_attempts),
+        ),
+        match &settings.journal {
+            Some(path) => Check::new(
+                "JIRA_JOURNAL",
+                CheckOutcome::Pass,
+                path.display().to_string(),
+            ),
+            None => Check::new("JIRA_JOURNAL", CheckOutcome::Warn, "off")
+                .hint("history and undo need the journal; unset JIRA_JOURNAL to turn it on"),
+        },
+        match &settings.story_points_field {
+            Some(field) => Check::new(
+                "JIRA_STORY_POINTS_FIELD",
+                CheckOutcome::Pass,
+                field.as_str(),
+            ),
+            None => Check::new(
+                "JIRA_STORY_POINTS_FIELD",
+                CheckOutcome::Pass,
+                "not set; the field is looked up by name",
+            ),
+        },
+    ]
+}
+
+async fn doctor_remote_checks(
+    client: &JiraClient,
+    settings: &Settings,
+    project: Option<&str>,
+    checks: &mut Vec<Check>,
+) {
+    let https = settings.base_url.starts_with("https://");
+    match client
+        .probe(&Request::get("serverInfo").action("server info"))
+        .await
+    {
+        Ok((status, info)) => {
+            let detail = match info.get("serverTitle").and_then(|t| t.as_str()) {
+                Some(title) => format!("{} answered ({title})", settings.base_url),
+                None => format!("{} answered with {status}", settings.base_url),
+            };
+            checks.push(Check::new("Connectivity", CheckOutcome::Pass, detail));
+            checks.push(if https {
+                Check::new("TLS", CheckOutcome::Pass, "certificate accepted")
+            } else {
+                Check::new("TLS", CheckOutcome::Warn, "connection is not encrypted")
+                    .hint("Jira Cloud sites are served over https://")
+            });
+        }
+        Err(err) => {
+            let message = format!("{err:#}");
+            let lower = message.to_lowercase();
+            if https && (lower.contains("certificate") || lower.contains("tls")) {
+                checks.push(Check::new(
+                    "Connectivity",
+                    CheckOutcome::Pass,
+                    "host reachable",
+                ));
+                checks.push(
+                    Check::new("TLS", CheckOutcome::Fail, message)
+                        .hint("Check for an intercepting proxy or a wrong JIRA_BASE_URL host"),
+                );
+            } else {
+                let timed_out = err
+                    .downcast_ref::<reqwest::Error>()
+                    .is_some_and(|err| err.is_timeout());
+                let hint = if timed_out {
+                    "The request timed out; check your network or proxy settings"
+                } else {
+                    "Check the JIRA_BASE_URL host name and your network/proxy settings"
+                };
+                checks.push(Check::new("Connectivity", CheckOutcome::Fail, message).hint(hint));
+                checks.push(Check::new("TLS", CheckOutcome::Skip, "site not reachable"));
+            }
+            for name in ["Authentication", "Project", "Permissions"] {
+                checks.push(Check::new(name, CheckOutcome::Skip, "site not reachable"));
+            }
+            return;
+        }
+    }
+
+    let authenticated = match client
+        .probe(&Request::get("myself").action("current user"))
+        .await
+    {
+        Ok((status, me)) if status.is_success() => {
+            let name = me
+                .get("displayName")
+                .and_then(|n| n.as_str())
+                .unwrap_or("unknown user");
+            checks.push(Check::new(
+                "Authentication",
+                CheckOutcome::Pass,
+                format!("logged in as {name}"),
+            ));
+            true
+        }
+        Ok((StatusCode::UNAUTHORIZED, _)) => {
+            checks.push(
+                Check::new(
+                    "Authentication",
+                    CheckOutcome::Fail,
+                    "Jira returned 401 Unauthorized",
+                )
+                .hint("Check JIRA_EMAIL and regenerate JIRA_API_TOKEN if it was revoked"),
+            );
+            false
+        }
+        Ok((StatusCode::FORBIDDEN, _)) => {
+            checks.push(
+                Check::new(
+                    "Authentication",
+                    CheckOutcome::Fail,
+                    "Jira returned 403 Forbidden",
+                )
+                .hint("The account may lack Jira access or need to pass a CAPTCHA in the browser"),
+            );
+            false
+        }
+        Ok((status, _)) => {
+            checks.push(Check::new(
+                "Authentication",
+                CheckOutcome::Fail,
+                format!("Jira returned {status}"),
+            ));
+            false
+        }
+        Err(err) => {
+            checks.push(Check::new(
+                "Authentication",
+                CheckOutcome::Fail,
+                format!("{err:#}"),
+            ));
+            false
+        }
+    };
+    if !authenticated {
+        for name in ["Project", "Permissions"] {
+            checks.push(Check::new(name, CheckOutcome::Skip, "not authenticated"));
+        }
+        return;
+    }
+
+    let Some(project) = project else {
+        for name in ["Project", "Permissions"] {
+            checks.push(Check::new(
+                name,
+                CheckOutcome::Skip,
+                "no project key configured",
+            ));
+        }
+        return;
+    };
+    match client
+        .probe(&Request::get(format!("project/{project}")).action("project"))
+        .await
+    {
+        Ok((status, info)) if status.is_success() => {
+            let name = info.get("name").and_then(|n| n.as_str()).unwrap_or(project);
+            checks.push(Check::new(
+                "Project",
+                CheckOutcome::Pass,
+                format!("{project} ({name})"),
+            ));
+        }
+        Ok((status, _)) => {
+            checks.push(
+                Check::new(
+                    "Project",
+                    CheckOutcome::Fail,
+                    format!("{project}: Jira returned {status}"),
+                )
+                .hint("Check the project key and that your account can browse the project"),
+            );
+            checks.push(Check::new(
+                "Permissions",
+                CheckOutcome::Skip,
+                "project not accessible",
+            ));
+            return;
+        }
+        Err(err) => {
+            checks.push(Check::new(
+                "Project",
+                CheckOutcome::Fail,
+                format!("{err:#}"),
+            ));
+            checks.push(Check::new(
+                "Permissions",
+                CheckOutcome::Skip,
+                "project not accessible",
+            ));
+            return;
+        }
+    }
+
+    let request = Request::get("mypermissions")
+        .query("projectKey", project)
+        .query("permissions", DOCTOR_PERMISSIONS.join(","))
+        .action("permissions");
+    match client.probe(&request).await {
+        Ok((status, payload)) if status.is_success() => {
+            let missing: Vec<&str> = DOCTOR_PERMISSIONS
+                .iter()
+                .copied()
+                .filter(|perm| {
+                    !payload
+                        .get("permissions")
+                        .and_then(|p| p.get(*perm))
+                        .and_then(|p| p.get("havePermission"))
+                        .and_then(|p| p.as_bool())
+                        .unwrap_or(false)
+                })
+                .collect();
+            checks.push(if missing.is_empty() {
+                Check::new("Permissions", CheckOutcome::Pass, DOCTOR_PERMISSIONS.join(", "))
+            } else {
+                Check::new(
+                    "Permissions",
+                    CheckOutcome::Fail,
+                    format!("missing {}", missing.join(", ")),
+                )
+                .hint("Ask a Jira admin to grant these permissions in the project's permission scheme")
+            });
+        }
+        Ok((status, _)) => {
+            checks.push(Check::new(
+                "Permissions",
+                CheckOutcome::Fail,
+                format!("Jira returned {status}"),
+            ));
+        }
+        Err(err) => {
+            checks.push(Check::new(
+                "Permissions",
+                CheckOutcome::Fail,
+                format!("{err:#}"),
+            ));
+        }
+    }
+}
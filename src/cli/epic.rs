@@ -0,0 +1,96 @@
+use anyhow::Result;
+use clap::Subcommand;
+use jico::{JiraClient, Settings};
+use serde_json::{Map, json};
+
+use super::{fail_if_any, print_json, resolve_project};
+
+#[derive(Subcommand, Debug)]
+pub enum EpicCommand {
+    /// List epics of a project, newest first
+    List {
+        /// Project key; falls back to config
+        #[arg(long)]
+        project: Option<String>,
+        /// Limit the number of results
+        #[arg(long, default_value_t = 20)]
+        limit: u32,
+    },
+    /// List all issues of an epic
+    Issues {
+        /// Epic key, e.g., PROJ-1
+        epic: String,
+    },
+    /// Put issues into an epic
+    Add {
+        /// Epic key, e.g., PROJ-1
+        epic: String,
+        /// Issue keys, e.g., PROJ-2 PROJ-3
+        #[arg(required = true)]
+        keys: Vec<String>,
+    },
+    /// Take issues out of an epic
+    Remove {
+        /// Epic key, e.g., PROJ-1
+        epic: String,
+        /// Issue keys, e.g., PROJ-2 PROJ-3
+        #[arg(required = true)]
+        keys: Vec<String>,
+    },
+    /// Show issue counts by status category and story point totals
+    Progress {
+        /// Epic key, e.g., PROJ-1
+        epic: String,
+    },
+}
+
+pub async fn run_epic(
+    client: &JiraClient,
+    settings: &Settings,
+    command: EpicCommand,
+) -> Result<()> {
+    match command {
+        EpicCommand::List { project, limit } => {
+            let project = resolve_project(settings, project)?;
+            let epics = client.list_epics(&project, limit).await?;
+            print_json(&epics.issues);
+        }
+        EpicCommand::Issues { epic } => {
+            let children = client.epic_children(&epic, &[]).await?;
+            print_json(&children);
+        }
+        EpicCommand::Add { epic, keys } => {
+            let mut added = Vec::new();
+            let mut failed = Map::new();
+            for key in keys {
+                match client.set_parent(&key, Some(&epic)).await {
+                    Ok(_) => added.push(key),
+                    Err(err) => {
+                        failed.insert(key, json!(format!("{err:#}")));
+                    }
+                }
+            }
+            print_json(&json!({ "epic": epic, "added": added, "failed": failed }));
+            fail_if_any(&failed)?;
+        }
+        EpicCommand::Remove { epic, keys } => {
+            let mut removed = Vec::new();
+            let mut failed = Map::new();
+            for key in keys {
+                match client.remove_from_epic(&epic, &key).await {
+                    Ok(_) => removed.push(key),
+                    Err(err) => {
+                        failed.insert(key, json!(format!("{err:#}")));
+                    }
+                }
+            }
+            print_json(&json!({ "epic": epic, "removed": removed, "failed": failed }));
+            fail_if_any(&failed)?;
+        }
+        EpicCommand::Progress { epic } => {
+            let progress = client.epic_progress(&epic).await?;
+            print_json(&progress);
+        }
+    }
+    Ok(())
+}
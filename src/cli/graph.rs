@@ -0,0 +1,30 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use jico::JiraClient;
+use jico::graph::BLOCKS;
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT
+    #[default]
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+}
+
+pub async fn run_graph(
+    client: &JiraClient,
+    jql: &str,
+    links: &[String],
+    format: GraphFormat,
+) -> Result<()> {
+    let graph = client.link_graph(jql, links).await?;
+    match format {
+        GraphFormat::Dot => print!("{}", graph.to_dot()),
+        GraphFormat::Mermaid => print!("{}", graph.to_mermaid()),
+    }
+    for cycle in graph.cycles(BLOCKS) {
+        eprintln!("Blocking loop: {}", cycle.join(" -> "));
+    }
+    Ok(())
+}
@@ -0,0 +1,239 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+use clap::{Args, ValueEnum};
+use jico::{Checkpoint, ErrorKind, ImportCheckpoint, ImportRow, JiraClient, JiraError};
+use serde_json::{Map, Value, json};
+
+use super::bulk::{default_checkpoint_path, field_value, settle_checkpoint};
+use super::print_json;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ImportFormat {
+    /// Header row plus one issue per line
+    Csv,
+    /// A list of issue objects
+    Json,
+    /// A list of issue mappings
+    Yaml,
+}
+
+impl ImportFormat {
+    fn name(self) -> &'static str {
+        match self {
+            ImportFormat::Csv => "csv",
+            ImportFormat::Json => "json",
+            ImportFormat::Yaml => "yaml",
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("csv") => Ok(ImportFormat::Csv),
+            Some("json") => Ok(ImportFormat::Json),
+            Some("yaml" | "yml") => Ok(ImportFormat::Yaml),
+            _ => Err(anyhow!(
+                "Cannot tell the format of {}; pass --format csv|json|yaml",
+                path.display()
+            )),
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct ImportArgs {
+    /// File with one issue per row/item
+    #[arg(required_unless_present = "resume")]
+    file: Option<PathBuf>,
+    /// File format; detected from the extension by default
+    #[arg(long, value_enum)]
+    format: Option<ImportFormat>,
+    /// Map file columns to fields, e.g. Title=summary,Type=issue_type
+    #[arg(long, value_delimiter = ',')]
+    map: Vec<String>,
+    /// Project for rows without a project column; falls back to config
+    #[arg(long)]
+    project: Option<String>,
+    /// Where to record progress; default: jico-import-<timestamp>.checkpoint.json
+    #[arg(long, conflicts_with = "resume")]
+    checkpoint: Option<PathBuf>,
+    /// Continue the run recorded in this checkpoint file
+    #[arg(long, conflicts_with_all = ["file", "format", "map", "project"])]
+    resume: Option<PathBuf>,
+    /// With --resume, retry only the rows that failed
+    #[arg(long, requires = "resume")]
+    retry_failed: bool,
+}
+
+pub async fn run_import(
+    client: &JiraClient,
+    args: ImportArgs,
+    default_project: Option<String>,
+) -> Result<()> {
+    let resuming = args.resume.is_some();
+    let (mut checkpoint, path) = match args.resume {
+        Some(path) => match Checkpoint::load(&path)? {
+            Checkpoint::Import(checkpoint) => (checkpoint, path),
+            Checkpoint::Bulk(_) => {
+                return Err(JiraError::new(
+                    ErrorKind::Validation,
+                    format!(
+                        "{} belongs to a bulk run; use jico bulk --resume",
+                        path.display()
+                    ),
+                )
+                .into());
+            }
+        },
+        None => {
+            let file = args.file.unwrap_or_default();
+            let format = match args.format {
+                Some(format) => format,
+                None => ImportFormat::from_path(&file)?,
+            };
+            let checkpoint = ImportCheckpoint {
+                file,
+                format: format.name().to_string(),
+                map: args.map,
+                project: args.project.or(default_project),
+                rows: 0,
+                created: BTreeMap::new(),
+                failed: BTreeMap::new(),
+            };
+            let path = args
+                .checkpoint
+                .unwrap_or_else(|| default_checkpoint_path("import"));
+            (checkpoint, path)
+        }
+    };
+
+    let format = ImportFormat::from_str(&checkpoint.format, true).map_err(|err| anyhow!(err))?;
+    let text = std::fs::read_to_string(&checkpoint.file)
+        .with_context(|| format!("Failed to read {}", checkpoint.file.display()))?;
+    let rows = parse_rows(&text, format, &parse_mappings(&checkpoint.map)?)?;
+    if resuming && rows.len() != checkpoint.rows {
+        return Err(JiraError::new(
+            ErrorKind::Validation,
+            format!(
+                "{} had {} rows when the checkpoint was written and has {} now",
+                checkpoint.file.display(),
+                checkpoint.rows,
+                rows.len()
+            ),
+        )
+        .into());
+    }
+    checkpoint.rows = rows.len();
+    let previous = if resuming {
+        checkpoint.previous(args.retry_failed)
+    } else {
+        BTreeMap::new()
+    };
+
+    // A dry run creates nothing, so there is no progress worth keeping.
+    let record = !client.dry_run;
+    if record {
+        checkpoint.save(&path)?;
+        eprintln!("Recording progress in {}", path.display());
+    }
+    let project = checkpoint.project.clone();
+    let outcomes = client
+        .import_rows(&rows, project.as_deref(), &previous, |row, outcome| {
+            checkpoint.record(row, outcome);
+            if record && let Err(err) = checkpoint.save(&path) {
+                eprintln!("Warning: {err:#}");
+            }
+        })
+        .await?;
+
+    let failed = outcomes.iter().filter(|o| o.error.is_some()).count();
+    // A dry run only plans the rows; their keys are `(row N)` placeholders.
+    let done = if record { "created" } else { "planned" };
+    print_json(&json!({
+        done: outcomes.len() - failed,
+        "failed": failed,
+        "rows": outcomes,
+    }));
+    if record {
+        settle_checkpoint(&path, checkpoint.is_complete());
+    }
+    if failed > 0 {
+        return Err(anyhow!("{failed} row(s) could not be created"));
+    }
+    Ok(())
+}
+
+/// `Title=summary` pairs from `--map`.
+fn parse_mappings(mappings: &[String]) -> Result<HashMap<String, String>> {
+    mappings
+        .iter()
+        .map(|mapping| {
+            let (column, field) = mapping
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Invalid --map '{mapping}' (expected COLUMN=field)"))?;
+            Ok((column.trim().to_string(), field.trim().to_string()))
+        })
+        .collect()
+}
+
+fn parse_rows(
+    text: &str,
+    format: ImportFormat,
+    mappings: &HashMap<String, String>,
+) -> Result<Vec<ImportRow>> {
+    let records: Vec<Map<String, Value>> = match format {
+        ImportFormat::Csv => {
+            let mut reader = csv::Reader::from_reader(text.as_bytes());
+            let headers = reader
+                .headers()
+                .context("Failed to read CSV header")?
+                .clone();
+            let mut records = Vec::new();
+            for (index, record) in reader.records().enumerate() {
+                let record =
+                    record.with_context(|| format!("Failed to read CSV row {}", index + 1))?;
+                // Empty cells mean "not set" rather than an empty value.
+                let cells = headers
+                    .iter()
+                    .zip(record.iter())
+                    .filter(|(_, cell)| !cell.trim().is_empty())
+                    .map(|(header, cell)| (header.trim().to_string(), json!(cell.trim())));
+                records.push(cells.collect());
+            }
+            records
+        }
+        ImportFormat::Json => {
+            serde_json::from_str(text).context("Expected a JSON list of issues")?
+        }
+        ImportFormat::Yaml => {
+            serde_yaml::from_str(text).context("Expected a YAML list of issues")?
+        }
+    };
+
+    records
+        .into_iter()
+        .enumerate()
+        .map(|(index, record)| {
+            let record: Map<String, Value> = record
+                .into_iter()
+                .map(|(key, value)| (mappings.get(&key).cloned().unwrap_or(key), value))
+                .collect();
+            let mut row: ImportRow = serde_json::from_value(Value::Object(record))
+                .with_context(|| format!("Invalid row {}", index + 1))?;
+            // CSV cells are all text; raw Jira fields such as story points need their type.
+            if format == ImportFormat::Csv {
+                for value in row.extra.values_mut() {
+                    if let Value::String(text) = value {
+                        *value = field_value(text);
+                    }
+                }
+            }
+            Ok(row)
+        })
+        .collect()
+}
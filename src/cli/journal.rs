@@ -0,0 +1,64 @@
+use anyhow::{Result, anyhow};
+use jico::{ErrorKind, JiraClient, JiraError, Journal};
+
+use super::print_json;
+
+/// The command line as typed, for telling operations apart in `history`.
+pub fn command_line() -> String {
+    std::env::args()
+        .enumerate()
+        .map(|(index, arg)| {
+            if index == 0 {
+                "jico".to_string()
+            } else if arg.is_empty() || arg.contains(char::is_whitespace) {
+                format!("{arg:?}")
+            } else {
+                arg
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn journal(client: &JiraClient) -> Result<&Journal> {
+    client.journal.as_ref().ok_or_else(|| {
+        JiraError::new(
+            ErrorKind::Config,
+            "The journal is off (unset JIRA_JOURNAL or set it to a file path to record changes)",
+        )
+        .into()
+    })
+}
+
+pub fn run_history(client: &JiraClient, limit: usize) -> Result<()> {
+    let mut operations = journal(client)?.operations()?;
+    operations.reverse();
+    operations.truncate(limit);
+    print_json(&operations);
+    Ok(())
+}
+
+pub async fn run_undo(client: &JiraClient, id: Option<u64>) -> Result<()> {
+    let journal = journal(client)?;
+    let operation = journal.undoable(id)?;
+    if id.is_none() {
+        // Newer operations with nothing to revert were passed over; say so.
+        for skipped in journal
+            .operations()?
+            .iter()
+            .filter(|op| op.id > operation.id && op.undoes.is_none() && op.undone_by.is_none())
+        {
+            eprintln!(
+                "Skipping operation {} ({}): deletes and moves cannot be undone",
+                skipped.id, skipped.command
+            );
+        }
+    }
+    let outcomes = client.undo(&operation).await;
+    print_json(&outcomes);
+    let failed = outcomes.iter().filter(|o| o.error.is_some()).count();
+    if failed > 0 {
+        return Err(anyhow!("{failed} change(s) could not be undone"));
+    }
+    Ok(())
+}
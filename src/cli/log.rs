@@ -0,0 +1,69 @@
+use anyhow::Result;
+use chrono::Local;
+use clap::ValueEnum;
+use jico::JiraClient;
+use jico::changelog::{TimelineEvent, parse_jira_time, timeline};
+
+use super::print_json;
+
+/// Longest from/to value shown in text output before it is cut short.
+const MAX_VALUE_CHARS: usize = 60;
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum LogFormat {
+    /// One line per change, in local time
+    #[default]
+    Text,
+    /// The timeline events as JSON
+    Json,
+}
+
+pub async fn run_log(
+    client: &JiraClient,
+    key: &str,
+    fields: &[String],
+    format: LogFormat,
+) -> Result<()> {
+    let changes = client.changelog(key).await?;
+    let events = timeline(&changes, fields);
+    match format {
+        LogFormat::Text => {
+            for event in &events {
+                println!("{}", describe(event));
+            }
+        }
+        LogFormat::Json => print_json(&events),
+    }
+    Ok(())
+}
+
+/// `2026-10-01 11:30  Ada  status: To Do -> In Progress`
+fn describe(event: &TimelineEvent) -> String {
+    let at = parse_jira_time(&event.at)
+        .map(|at| {
+            at.with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| event.at.clone());
+    format!(
+        "{at}  {}  {}: {} -> {}",
+        event.author.as_deref().unwrap_or("(unknown)"),
+        event.field,
+        shorten(event.from.as_deref()),
+        shorten(event.to.as_deref())
+    )
+}
+
+fn shorten(value: Option<&str>) -> String {
+    let value = value.map(str::trim).filter(|v| !v.is_empty());
+    let Some(value) = value else {
+        return "(none)".to_string();
+    };
+    let value = value.replace('\n', " ");
+    if value.chars().count() <= MAX_VALUE_CHARS {
+        return value;
+    }
+    let cut: String = value.chars().take(MAX_VALUE_CHARS - 1).collect();
+    format!("{cut}…")
+}
@@ -0,0 +1,624 @@
+use std::process::ExitCode;
+
+use anyhow::{Result, anyhow};
+use clap::{Parser, Subcommand, ValueEnum};
+use dotenvy::dotenv;
+use jico::{
+    CloneOptions, ErrorKind, JiraClient, JiraError, Journal, LinkRelation, NewIssue, Settings,
+    TreeOptions, description_to_adf,
+};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+mod agile;
+mod bulk;
+mod delete;
+mod doctor;
+mod epic;
+mod graph;
+mod import;
+mod journal;
+mod log;
+mod report;
+mod standup;
+mod tree;
+
+#[derive(Parser, Debug)]
+#[command(name = "jico", version, about = "CLI helper for Jira Cloud")]
+pub struct Cli {
+    /// How to print errors on stderr
+    #[arg(long, global = true, value_enum, default_value_t = ErrorFormat::Text)]
+    pub error_format: ErrorFormat,
+    /// Print the requests that would change something instead of sending them
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+    #[command(subcommand)]
+    pub command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Commands {
+    /// Create a new issue
+    Create {
+        /// Summary/title of the issue
+        summary: String,
+        /// Optional description (plain text)
+        #[arg(long)]
+        description: Option<String>,
+        /// Project key; falls back to config
+        #[arg(long)]
+        project: Option<String>,
+        /// Issue type name; default: Task (or Sub-task when --parent is set)
+        #[arg(long)]
+        issue_type: Option<String>,
+        /// Parent issue key (create as sub-task)
+        #[arg(long)]
+        parent: Option<String>,
+        /// Labels to set (comma-separated or repeated)
+        #[arg(long, value_delimiter = ',')]
+        labels: Option<Vec<String>>,
+        /// Priority name
+        #[arg(long)]
+        priority: Option<String>,
+        /// Assignee accountId
+        #[arg(long)]
+        assignee: Option<String>,
+    },
+    /// List issues via JQL
+    List {
+        /// Optional JQL override
+        #[arg(long)]
+        jql: Option<String>,
+        /// Limit the number of results
+        #[arg(long, default_value_t = 20)]
+        limit: u32,
+        /// Project key to build default JQL
+        #[arg(long)]
+        project: Option<String>,
+    },
+    /// Show a single issue
+    View {
+        /// Issue key, e.g., PROJ-123
+        key: String,
+        /// Show only subtasks
+        #[arg(long)]
+        subtasks: bool,
+    },
+    /// Update issue fields
+    Update {
+        /// Issue key, e.g., PROJ-123
+        key: String,
+        /// New summary/title
+        #[arg(long)]
+        summary: Option<String>,
+        /// New description (plain text)
+        #[arg(long)]
+        description: Option<String>,
+        /// Move issue to another project (project key)
+        #[arg(long)]
+        project: Option<String>,
+        /// New issue type name
+        #[arg(long)]
+        issue_type: Option<String>,
+        /// Parent issue key (set as sub-task)
+        #[arg(long)]
+        parent: Option<String>,
+        /// Labels to set (comma-separated or repeated)
+        #[arg(long, value_delimiter = ',')]
+        labels: Option<Vec<String>>,
+        /// Priority name
+        #[arg(long)]
+        priority: Option<String>,
+        /// Assignee accountId
+        #[arg(long)]
+        assignee: Option<String>,
+    },
+    /// Transition an issue to a new status/transition
+    Transition {
+        /// Issue key, e.g., PROJ-123
+        key: String,
+        /// Target status/transition name
+        #[arg(long)]
+        to: String,
+    },
+    /// Link two issues
+    Link {
+        /// Issue key, e.g., PROJ-123
+        key: String,
+        /// Target issue key, e.g., PROJ-456
+        #[arg(long)]
+        to: String,
+        /// Link relation from issue key to target issue
+        #[arg(long, value_enum, default_value_t = LinkRelation::Blocks)]
+        relation: LinkRelation,
+    },
+    /// Delete an issue after confirmation
+    Delete {
+        /// Issue key, e.g., PROJ-123
+        key: String,
+        /// Delete the issue's sub-tasks too
+        #[arg(long)]
+        delete_subtasks: bool,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Copy an issue with its fields (custom ones included) and link it to the original
+    Clone {
+        /// Issue key, e.g., PROJ-123
+        key: String,
+        /// Clone the sub-tasks too
+        #[arg(long)]
+        with_subtasks: bool,
+        /// Copy the original's issue links to the clone
+        #[arg(long)]
+        with_links: bool,
+        /// Text put in front of the summary
+        #[arg(long, default_value = "CLONE - ")]
+        summary_prefix: String,
+        /// Project to create the clone in; default: the original's
+        #[arg(long)]
+        project: Option<String>,
+    },
+    /// Move an issue to another project
+    Move {
+        /// Issue key, e.g., PROJ-123
+        key: String,
+        /// Target project key
+        #[arg(long)]
+        project: String,
+        /// Issue type in the target project
+        #[arg(long)]
+        issue_type: String,
+    },
+    /// Turn a standard issue into a sub-task, or a sub-task into a standard issue
+    Convert {
+        /// Issue key, e.g., PROJ-123
+        key: String,
+        /// Make the issue a sub-task of this issue
+        #[arg(
+            long,
+            conflicts_with = "to_standard",
+            required_unless_present = "to_standard"
+        )]
+        to_subtask_of: Option<String>,
+        /// Make the sub-task a standard issue (needs --issue-type)
+        #[arg(long, requires = "issue_type")]
+        to_standard: bool,
+        /// Target issue type; default for sub-tasks: the project's first sub-task type
+        #[arg(long)]
+        issue_type: Option<String>,
+    },
+    /// Give issues a new parent, checking that the hierarchy allows it
+    Reparent {
+        /// Issue keys, e.g., PROJ-2 PROJ-3
+        #[arg(required = true)]
+        keys: Vec<String>,
+        /// New parent issue key
+        #[arg(long)]
+        parent: String,
+    },
+    /// Fold a duplicate into the issue it duplicates and close it
+    Merge {
+        /// The duplicate issue, e.g., PROJ-124
+        duplicate: String,
+        /// The issue to keep, e.g., PROJ-123
+        #[arg(long)]
+        into: String,
+    },
+    /// Show an issue's changelog: field changes and transitions, oldest first
+    Log {
+        /// Issue key, e.g., PROJ-123
+        key: String,
+        /// Only show changes to these fields, by name or id (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        field: Vec<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = log::LogFormat::Text)]
+        format: log::LogFormat,
+    },
+    /// Flow reports built from issue changelogs
+    Report {
+        #[command(subcommand)]
+        command: report::ReportCommand,
+    },
+    /// Summarize a user's recent activity and open work as Markdown
+    Standup {
+        /// User: `me`, an account id, email or display name
+        #[arg(long, default_value = "me")]
+        user: String,
+        /// Start of the window: today, yesterday, YYYY-MM-DD, an RFC 3339 time or an age like 36h
+        #[arg(long, default_value = "yesterday")]
+        since: String,
+    },
+    /// List recent changes recorded in the journal (JIRA_JOURNAL=off disables it), newest first
+    History {
+        /// Limit the number of operations
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Revert a recorded operation: restore fields, move issues back, remove links.
+    /// Deletes and moves (including convert and reparent) are listed in history but cannot be undone
+    Undo {
+        /// Operation id from `history`; default: the latest one not undone yet that can be
+        id: Option<u64>,
+    },
+    /// Apply one change to every issue matching a JQL query
+    Bulk(bulk::BulkArgs),
+    /// Create issues in bulk from a CSV, JSON or YAML file
+    Import(import::ImportArgs),
+    /// Show an issue's hierarchy (epic → issues → sub-tasks) as a tree
+    Tree {
+        /// Issue key, e.g., PROJ-1
+        key: String,
+        /// Levels below the issue to expand
+        #[arg(long, default_value_t = 3)]
+        depth: u32,
+        /// Also follow issue links
+        #[arg(long)]
+        links: bool,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = tree::TreeFormat::Text)]
+        format: tree::TreeFormat,
+    },
+    /// Export the link graph of matching issues as Graphviz DOT or Mermaid
+    Graph {
+        /// Issues to include
+        #[arg(long)]
+        jql: String,
+        /// Link types to follow, by name or outward verb (comma-separated)
+        #[arg(long, value_delimiter = ',', default_value = "blocks")]
+        links: Vec<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = graph::GraphFormat::Dot)]
+        format: graph::GraphFormat,
+    },
+    /// List the unresolved issues blocking an issue, directly or transitively
+    Blockers {
+        /// Issue key, e.g., PROJ-123
+        key: String,
+    },
+    /// Show the longest chain of unresolved blockers behind an epic's open issues
+    CriticalPath {
+        /// Epic key, e.g., PROJ-1
+        #[arg(long)]
+        epic: String,
+    },
+    /// Jira Software boards
+    Board {
+        #[command(subcommand)]
+        command: agile::BoardCommand,
+    },
+    /// Jira Software sprints
+    Sprint {
+        #[command(subcommand)]
+        command: agile::SprintCommand,
+    },
+    /// Epics and their issues
+    Epic {
+        #[command(subcommand)]
+        command: epic::EpicCommand,
+    },
+    /// Show the account jico authenticates as
+    Whoami,
+    /// Check configuration, connectivity, authentication and project permissions
+    Doctor {
+        /// Project key to check; falls back to config
+        #[arg(long)]
+        project: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum ErrorFormat {
+    /// Human-readable message on stderr
+    #[default]
+    Text,
+    /// A JSON object on stderr
+    Json,
+}
+
+pub fn report_error(err: &anyhow::Error, format: ErrorFormat) -> ExitCode {
+    let kind = ErrorKind::of(err);
+    match format {
+        ErrorFormat::Text => eprintln!("Error: {err:#}"),
+        ErrorFormat::Json => {
+            let value = match err.chain().find_map(|c| c.downcast_ref::<JiraError>()) {
+                Some(jira) if err.chain().count() == 1 => jira.to_json(),
+                _ => json!({
+                    "error": {
+                        "kind": kind.name(),
+                        "status": null,
+                        "messages": [format!("{err:#}")],
+                        "errors": {},
+                        "exitCode": kind.exit_code(),
+                    }
+                }),
+            };
+            eprintln!("{value}");
+        }
+    }
+    ExitCode::from(kind.exit_code())
+}
+
+pub async fn run(cli: Cli) -> Result<()> {
+    dotenv().ok(); // load from .env in current working dir; won't override real env vars
+    if let Commands::Doctor { project } = cli.command {
+        return doctor::run_doctor(project).await;
+    }
+    let settings = Settings::load()?;
+    let mut client = JiraClient::new(&settings)?;
+    client.dry_run = cli.dry_run;
+    client.journal = settings
+        .journal
+        .as_ref()
+        .map(|path| Journal::new(path, &journal::command_line()));
+
+    match cli.command {
+        Commands::Create {
+            summary,
+            description,
+            project,
+            issue_type,
+            parent,
+            labels,
+            priority,
+            assignee,
+        } => {
+            let project_key = resolve_project(&settings, project)?;
+            let issue_type = issue_type.unwrap_or_else(|| {
+                if parent.is_some() {
+                    "Sub-task".to_string()
+                } else {
+                    "Task".to_string()
+                }
+            });
+            let issue = NewIssue {
+                description,
+                parent,
+                labels,
+                priority,
+                assignee,
+                ..NewIssue::new(&project_key, &summary, &issue_type)
+            };
+            let created = client.create_issue(&issue).await?;
+            print_json(&created);
+        }
+        Commands::List {
+            jql,
+            limit,
+            project,
+        } => {
+            let jql = jql
+                .or_else(|| settings.default_jql.clone())
+                .or_else(|| {
+                    resolve_project(&settings, project)
+                        .ok()
+                        .map(|key| format!("project = {} ORDER BY created DESC", key))
+                })
+                .ok_or_else(|| {
+                    JiraError::new(
+                        ErrorKind::Config,
+                        "Provide --jql or configure a project key",
+                    )
+                })?;
+            let results = client.list_issues(&jql, limit).await?;
+            print_json(&results);
+        }
+        Commands::View { key, subtasks } => {
+            if subtasks {
+                let list = client.get_issue_subtasks(&key).await?;
+                print_json(&list);
+            } else {
+                let issue = client.get_issue(&key).await?;
+                print_json(&issue);
+            }
+        }
+        Commands::Update {
+            key,
+            summary,
+            description,
+            project,
+            issue_type,
+            parent,
+            labels,
+            priority,
+            assignee,
+        } => {
+            let mut fields = Map::new();
+            if let Some(summary) = summary {
+                fields.insert("summary".to_string(), json!(summary));
+            }
+            if let Some(description) = description {
+                fields.insert("description".to_string(), description_to_adf(&description));
+            }
+            if let Some(project) = project {
+                fields.insert("project".to_string(), json!({ "key": project }));
+            }
+            let issue_type = issue_type.or_else(|| {
+                if parent.is_some() {
+                    Some("Sub-task".to_string())
+                } else {
+                    None
+                }
+            });
+            if let Some(issue_type) = issue_type {
+                fields.insert("issuetype".to_string(), json!({ "name": issue_type }));
+            }
+            if let Some(parent) = parent {
+                fields.insert("parent".to_string(), json!({ "key": parent }));
+            }
+            if let Some(labels) = labels {
+                fields.insert("labels".to_string(), json!(labels));
+            }
+            if let Some(priority) = priority {
+                fields.insert("priority".to_string(), json!({ "name": priority }));
+            }
+            if let Some(assignee) = assignee {
+                fields.insert("assignee".to_string(), json!({ "accountId": assignee }));
+            }
+            if fields.is_empty() {
+                return Err(anyhow!(
+                    "Provide at least one field to update (--summary, --description, --project, --issue-type, --parent, --labels, --priority, --assignee)"
+                ));
+            }
+            let updated = client.update_issue(&key, fields).await?;
+            print_json(&updated);
+        }
+        Commands::Transition { key, to } => {
+            let result = client.transition_issue(&key, &to).await?;
+            print_json(&result);
+        }
+        Commands::Link { key, to, relation } => {
+            let result = client.link_issues(&key, &to, relation).await?;
+            print_json(&result);
+        }
+        Commands::Delete {
+            key,
+            delete_subtasks,
+            yes,
+        } => delete::run_delete(&client, &key, delete_subtasks, yes).await?,
+        Commands::Clone {
+            key,
+            with_subtasks,
+            with_links,
+            summary_prefix,
+            project,
+        } => {
+            let options = CloneOptions {
+                project,
+                summary_prefix,
+                with_subtasks,
+                with_links,
+            };
+            let report = client.clone_issue(&key, &options).await?;
+            print_json(&report);
+        }
+        Commands::Move {
+            key,
+            project,
+            issue_type,
+        } => {
+            let moved = client.move_issue(&key, &project, &issue_type).await?;
+            print_json(&json!({
+                "key": key,
+                "movedTo": moved.as_ref().map(|issue| &issue.key),
+            }));
+        }
+        Commands::Convert {
+            key,
+            to_subtask_of,
+            to_standard: _,
+            issue_type,
+        } => {
+            let converted = match &to_subtask_of {
+                Some(parent) => {
+                    client
+                        .convert_to_subtask(&key, parent, issue_type.as_deref())
+                        .await?
+                }
+                None => {
+                    let issue_type = issue_type.unwrap_or_default();
+                    client.convert_to_standard(&key, &issue_type).await?
+                }
+            };
+            print_json(&json!({
+                "key": key,
+                "issueType": converted
+                    .as_ref()
+                    .and_then(|issue| issue.fields.issuetype.as_ref())
+                    .map(|t| &t.name),
+                "parent": converted
+                    .as_ref()
+                    .and_then(|issue| issue.fields.parent.as_ref())
+                    .map(|parent| &parent.key),
+            }));
+        }
+        Commands::Reparent { keys, parent } => {
+            let mut reparented = Vec::new();
+            let mut failed = Map::new();
+            for key in keys {
+                match client.reparent(&key, &parent).await {
+                    Ok(()) => reparented.push(key),
+                    Err(err) => {
+                        failed.insert(key, json!(format!("{err:#}")));
+                    }
+                }
+            }
+            print_json(&json!({ "parent": parent, "reparented": reparented, "failed": failed }));
+            fail_if_any(&failed)?;
+        }
+        Commands::Merge { duplicate, into } => {
+            let report = client.merge_issues(&duplicate, &into).await?;
+            print_json(&report);
+        }
+        Commands::Log { key, field, format } => log::run_log(&client, &key, &field, format).await?,
+        Commands::Report { command } => report::run_report(&client, command).await?,
+        Commands::Standup { user, since } => standup::run_standup(&client, &user, &since).await?,
+        Commands::History { limit } => journal::run_history(&client, limit)?,
+        Commands::Undo { id } => journal::run_undo(&client, id).await?,
+        Commands::Bulk(args) => bulk::run_bulk(&client, args).await?,
+        Commands::Import(args) => {
+            import::run_import(&client, args, settings.project_key.clone()).await?
+        }
+        Commands::Tree {
+            key,
+            depth,
+            links,
+            format,
+        } => tree::run_tree(&client, &key, TreeOptions { depth, links }, format).await?,
+        Commands::Graph { jql, links, format } => {
+            graph::run_graph(&client, &jql, &links, format).await?
+        }
+        Commands::Blockers { key } => {
+            let blockers = client.blockers(&key).await?;
+            print_json(&blockers);
+        }
+        Commands::CriticalPath { epic } => {
+            let path = client.critical_path(&epic).await?;
+            print_json(&path);
+        }
+        Commands::Board { command } => agile::run_board(&client, command).await?,
+        Commands::Sprint { command } => agile::run_sprint(&client, command).await?,
+        Commands::Epic { command } => epic::run_epic(&client, &settings, command).await?,
+        Commands::Whoami => {
+            let me = client.myself().await?;
+            print_json(&json!({
+                "accountId": me.account_id,
+                "displayName": me.display_name,
+                "emailAddress": me.email_address,
+                "timeZone": me.time_zone,
+            }));
+        }
+        Commands::Doctor { .. } => unreachable!("doctor runs before settings are loaded"),
+    }
+
+    Ok(())
+}
+
+fn resolve_project(settings: &Settings, override_key: Option<String>) -> Result<String> {
+    override_key
+        .or_else(|| settings.project_key.clone())
+        .ok_or_else(|| {
+            JiraError::new(
+                ErrorKind::Config,
+                "Project key is required (pass --project or set JIRA_PROJECT_KEY)",
+            )
+            .into()
+        })
+}
+
+fn print_json<T: Serialize + ?Sized>(value: &T) {
+    match serde_json::to_string_pretty(value) {
+        Ok(s) => println!("{s}"),
+        Err(err) => eprintln!("Failed to render JSON output: {err}"),
+    }
+}
+
+/// The per-issue report is already printed; this only sets a failing exit status.
+fn fail_if_any(failed: &Map<String, Value>) -> Result<()> {
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("{} issue(s) could not be updated", failed.len()))
+    }
+}
@@ -0,0 +1,504 @@
+use std::io;
+
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use clap::{Subcommand, ValueEnum};
+use jico::JiraClient;
+use jico::cycle_time::{CycleTimeOptions, CycleTimeReport, Percentiles};
+use jico::flow::{CumulativeFlow, Interval};
+use jico::timesheet::{GroupBy, Timesheet, parse_timezone};
+use jico::velocity::{Burndown, SprintVelocity};
+
+use super::print_json;
+
+#[derive(Subcommand, Debug)]
+pub enum ReportCommand {
+    /// Cycle time, lead time and time in each status of matching issues
+    CycleTime {
+        /// Issues to measure
+        #[arg(long)]
+        jql: String,
+        /// Statuses that start the cycle (comma-separated)
+        #[arg(long, value_delimiter = ',', default_value = "In Progress")]
+        start: Vec<String>,
+        /// Statuses that end it (comma-separated)
+        #[arg(long, value_delimiter = ',', default_value = "Done")]
+        done: Vec<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ReportFormat::Table)]
+        format: ReportFormat,
+    },
+    /// Committed vs. completed story points of a board's last closed sprints
+    Velocity {
+        /// Board id
+        #[arg(long)]
+        board: u64,
+        /// Number of closed sprints
+        #[arg(long, default_value_t = 6)]
+        last: usize,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ReportFormat::Table)]
+        format: ReportFormat,
+    },
+    /// Remaining story points of a sprint, day by day
+    Burndown {
+        /// Sprint id
+        #[arg(long)]
+        sprint: u64,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ChartFormat::Chart)]
+        format: ChartFormat,
+    },
+    /// Issues per status over time, for a cumulative flow diagram
+    Cfd {
+        /// Issues to count
+        #[arg(long)]
+        jql: String,
+        /// First day (YYYY-MM-DD)
+        #[arg(long)]
+        from: NaiveDate,
+        /// Last day (YYYY-MM-DD); default: today
+        #[arg(long)]
+        to: Option<NaiveDate>,
+        /// Spacing of the samples
+        #[arg(long, value_enum, default_value_t = Interval::Day)]
+        interval: Interval,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ChartFormat::Csv)]
+        format: ChartFormat,
+    },
+    /// Hours users logged, per day, issue or epic
+    Timesheet {
+        /// Users: `me`, email addresses, display names or account ids (comma-separated)
+        #[arg(long, value_delimiter = ',', required = true)]
+        users: Vec<String>,
+        /// First day (YYYY-MM-DD)
+        #[arg(long)]
+        from: NaiveDate,
+        /// Last day (YYYY-MM-DD); default: today
+        #[arg(long)]
+        to: Option<NaiveDate>,
+        /// What each row stands for
+        #[arg(long, value_enum, default_value_t = GroupBy::Day)]
+        group_by: GroupBy,
+        /// IANA timezone to count days in, e.g. Europe/Berlin; default: each user's own
+        #[arg(long)]
+        timezone: Option<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ReportFormat::Table)]
+        format: ReportFormat,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum ReportFormat {
+    /// Aligned columns for the terminal
+    #[default]
+    Table,
+    /// Comma-separated values with a header row
+    Csv,
+    /// The full report as JSON
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum ChartFormat {
+    /// A bar per day for the terminal
+    #[default]
+    Chart,
+    /// Comma-separated values with a header row
+    Csv,
+    /// The full series as JSON
+    Json,
+}
+
+/// Width of the longest bar in terminal charts.
+const CHART_WIDTH: usize = 40;
+/// Fill of each status band in the cumulative flow chart, done first.
+const BAND_FILLS: [char; 6] = ['█', '▓', '▒', '░', '#', '+'];
+
+pub async fn run_report(client: &JiraClient, command: ReportCommand) -> Result<()> {
+    match command {
+        ReportCommand::CycleTime {
+            jql,
+            start,
+            done,
+            format,
+        } => {
+            let options = CycleTimeOptions { start, done };
+            let report = client.cycle_time_report(&jql, &options).await?;
+            match format {
+                ReportFormat::Table => print!("{}", cycle_time_table(&report)),
+                ReportFormat::Csv => write_csv(&cycle_time_rows(&report, true))?,
+                ReportFormat::Json => print_json(&report),
+            }
+        }
+        ReportCommand::Velocity {
+            board,
+            last,
+            format,
+        } => {
+            let velocity = client.velocity(board, last).await?;
+            match format {
+                ReportFormat::Table => print!("{}", velocity_table(&velocity)),
+                ReportFormat::Csv => write_csv(&velocity_rows(&velocity))?,
+                ReportFormat::Json => print_json(&velocity),
+            }
+        }
+        ReportCommand::Burndown { sprint, format } => {
+            let burndown = client.burndown(sprint).await?;
+            match format {
+                ChartFormat::Chart => print!("{}", burndown_chart(&burndown)),
+                ChartFormat::Csv => write_csv(&burndown_rows(&burndown))?,
+                ChartFormat::Json => print_json(&burndown),
+            }
+        }
+        ReportCommand::Cfd {
+            jql,
+            from,
+            to,
+            interval,
+            format,
+        } => {
+            let to = to.unwrap_or_else(|| Local::now().date_naive());
+            let flow = client
+                .cumulative_flow(&jql, from, to, interval, &Local)
+                .await?;
+            match format {
+                ChartFormat::Chart => print!("{}", flow_chart(&flow)),
+                ChartFormat::Csv => write_csv(&flow_rows(&flow))?,
+                ChartFormat::Json => print_json(&flow),
+            }
+        }
+        ReportCommand::Timesheet {
+            users,
+            from,
+            to,
+            group_by,
+            timezone,
+            format,
+        } => {
+            let timezone = timezone.as_deref().map(parse_timezone).transpose()?;
+            let to = to.unwrap_or_else(|| Local::now().date_naive());
+            let sheet = client
+                .timesheet(&users, from, to, group_by, timezone)
+                .await?;
+            match format {
+                ReportFormat::Table => {
+                    print!("{}", render_table(&timesheet_rows(&sheet, group_by)))
+                }
+                ReportFormat::Csv => write_csv(&timesheet_rows(&sheet, group_by))?,
+                ReportFormat::Json => print_json(&sheet),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn days(value: Option<f64>) -> String {
+    value.map(|d| format!("{d:.2}")).unwrap_or_default()
+}
+
+/// Statuses seen across all issues, in order of first appearance.
+fn statuses(report: &CycleTimeReport) -> Vec<&str> {
+    let mut statuses: Vec<&str> = Vec::new();
+    for time in report.issues.iter().flat_map(|row| &row.time_in_status) {
+        if !statuses.contains(&time.status.as_str()) {
+            statuses.push(&time.status);
+        }
+    }
+    statuses
+}
+
+/// Header plus one row per issue; with `percentiles`, followed by p50/p85/p95
+/// rows per issue type, keyed by the percentile's name.
+fn cycle_time_rows(report: &CycleTimeReport, percentiles: bool) -> Vec<Vec<String>> {
+    let statuses = statuses(report);
+    let mut header: Vec<String> = ["Key", "Type", "Status", "Cycle (d)", "Lead (d)"]
+        .map(String::from)
+        .to_vec();
+    header.extend(statuses.iter().map(|s| format!("{s} (d)")));
+    let mut rows = vec![header];
+    for issue in &report.issues {
+        let mut row = vec![
+            issue.key.clone(),
+            issue.issue_type.clone(),
+            issue.status.clone(),
+            days(issue.cycle_time_days),
+            days(issue.lead_time_days),
+        ];
+        row.extend(statuses.iter().map(|status| {
+            days(
+                issue
+                    .time_in_status
+                    .iter()
+                    .find(|t| t.status == *status)
+                    .map(|t| t.days),
+            )
+        }));
+        rows.push(row);
+    }
+    if percentiles {
+        for summary in &report.summary {
+            let cycle = percentile_cells(summary.cycle_time);
+            let lead = percentile_cells(summary.lead_time);
+            for (i, name) in PERCENTILE_NAMES.iter().enumerate() {
+                let mut row = vec![
+                    name.to_string(),
+                    summary
+                        .issue_type
+                        .clone()
+                        .unwrap_or_else(|| "All".to_string()),
+                    String::new(),
+                    cycle[i].clone(),
+                    lead[i].clone(),
+                ];
+                row.resize(statuses.len() + 5, String::new());
+                rows.push(row);
+            }
+        }
+    }
+    rows
+}
+
+const PERCENTILE_NAMES: [&str; 3] = ["p50", "p85", "p95"];
+
+fn percentile_cells(percentiles: Option<Percentiles>) -> [String; 3] {
+    match percentiles {
+        Some(p) => [p.p50, p.p85, p.p95].map(|d| days(Some(d))),
+        None => Default::default(),
+    }
+}
+
+fn cycle_time_table(report: &CycleTimeReport) -> String {
+    let mut out = render_table(&cycle_time_rows(report, false));
+    let mut summary = vec![
+        [
+            "Type",
+            "Done",
+            "Cycle p50",
+            "Cycle p85",
+            "Cycle p95",
+            "Lead p50",
+            "Lead p85",
+            "Lead p95",
+        ]
+        .map(String::from)
+        .to_vec(),
+    ];
+    for s in &report.summary {
+        let mut row = vec![
+            s.issue_type.clone().unwrap_or_else(|| "All".to_string()),
+            s.count.to_string(),
+        ];
+        for percentiles in [s.cycle_time, s.lead_time] {
+            row.extend(percentile_cells(percentiles));
+        }
+        summary.push(row);
+    }
+    out.push('\n');
+    out.push_str(&render_table(&summary));
+    out
+}
+
+fn points(value: f64) -> String {
+    format!("{value:.1}")
+}
+
+/// `YYYY-MM-DD` of a Jira timestamp.
+fn date(value: Option<&str>) -> String {
+    let value = value.unwrap_or_default();
+    value.get(..10).unwrap_or(value).to_string()
+}
+
+fn velocity_rows(velocity: &[SprintVelocity]) -> Vec<Vec<String>> {
+    let mut rows = vec![
+        ["Sprint", "Start", "Closed", "Committed", "Completed"]
+            .map(String::from)
+            .to_vec(),
+    ];
+    for sprint in velocity {
+        rows.push(vec![
+            sprint.name.clone(),
+            date(sprint.start_date.as_deref()),
+            date(sprint.complete_date.as_deref()),
+            points(sprint.committed),
+            points(sprint.completed),
+        ]);
+    }
+    rows
+}
+
+fn velocity_table(velocity: &[SprintVelocity]) -> String {
+    let mut rows = velocity_rows(velocity);
+    if !velocity.is_empty() {
+        let average = |f: fn(&SprintVelocity) -> f64| {
+            points(velocity.iter().map(f).sum::<f64>() / velocity.len() as f64)
+        };
+        rows.push(vec![
+            "Average".to_string(),
+            String::new(),
+            String::new(),
+            average(|s| s.committed),
+            average(|s| s.completed),
+        ]);
+    }
+    render_table(&rows)
+}
+
+fn burndown_rows(burndown: &Burndown) -> Vec<Vec<String>> {
+    let mut rows = vec![["Date", "Remaining", "Ideal"].map(String::from).to_vec()];
+    for day in &burndown.days {
+        rows.push(vec![
+            day.date.clone(),
+            points(day.remaining),
+            points(day.ideal),
+        ]);
+    }
+    rows
+}
+
+/// One bar of remaining points per day, with `|` marking the ideal line:
+///
+/// `2026-10-02  ██████████████|          5.0  (ideal 6.7)`
+fn burndown_chart(burndown: &Burndown) -> String {
+    let top = burndown
+        .days
+        .iter()
+        .map(|day| day.remaining)
+        .fold(burndown.committed, f64::max);
+    let scale = |value: f64| {
+        if top > 0.0 {
+            (value / top * CHART_WIDTH as f64).round() as usize
+        } else {
+            0
+        }
+    };
+    let mut out = format!(
+        "{}: {} points committed\n",
+        burndown.sprint.name,
+        points(burndown.committed)
+    );
+    for day in &burndown.days {
+        let mut bar: Vec<char> = vec![' '; CHART_WIDTH + 1];
+        bar.iter_mut()
+            .take(scale(day.remaining))
+            .for_each(|cell| *cell = '█');
+        bar[scale(day.ideal)] = '|';
+        let bar: String = bar.into_iter().collect();
+        out.push_str(&format!(
+            "{}  {bar}  {:>5}  (ideal {})\n",
+            day.date,
+            points(day.remaining),
+            points(day.ideal)
+        ));
+    }
+    out
+}
+
+fn flow_rows(flow: &CumulativeFlow) -> Vec<Vec<String>> {
+    let mut header = vec!["Date".to_string()];
+    header.extend(flow.statuses.iter().cloned());
+    let mut rows = vec![header];
+    for point in &flow.points {
+        let mut row = vec![point.date.clone()];
+        row.extend(point.counts.iter().map(usize::to_string));
+        rows.push(row);
+    }
+    rows
+}
+
+/// One stacked bar per sample, done statuses on the left, with a legend:
+///
+/// `2026-10-02  ██████▓▓▓▓▒▒▒▒▒▒▒▒  9`
+fn flow_chart(flow: &CumulativeFlow) -> String {
+    let top = flow
+        .points
+        .iter()
+        .map(|p| p.counts.iter().sum::<usize>())
+        .max()
+        .unwrap_or(0);
+    let fill = |i: usize| BAND_FILLS[(flow.statuses.len() - 1 - i) % BAND_FILLS.len()];
+    let legend: Vec<String> = (0..flow.statuses.len())
+        .rev()
+        .map(|i| format!("{} {}", fill(i), flow.statuses[i]))
+        .collect();
+    let mut out = format!("{}\n", legend.join("  "));
+    for point in &flow.points {
+        let mut bar = String::new();
+        let mut total = 0;
+        let mut drawn = 0;
+        for (i, count) in point.counts.iter().enumerate().rev() {
+            total += count;
+            // Scaling the running total, not each count, keeps bar lengths true to the totals.
+            let end = (total * CHART_WIDTH).checked_div(top).unwrap_or(0);
+            bar.extend(std::iter::repeat_n(fill(i), end - drawn));
+            drawn = end;
+        }
+        out.push_str(&format!(
+            "{}  {bar:<width$}  {total}\n",
+            point.date,
+            width = CHART_WIDTH
+        ));
+    }
+    out
+}
+
+/// A row per group and a column per user, with totals on both sides.
+fn timesheet_rows(sheet: &Timesheet, group_by: GroupBy) -> Vec<Vec<String>> {
+    let first = match group_by {
+        GroupBy::Day => "Date",
+        GroupBy::Issue => "Issue",
+        GroupBy::Epic => "Epic",
+    };
+    let mut header = vec![first.to_string()];
+    header.extend(sheet.users.iter().cloned());
+    header.push("Total".to_string());
+    let mut rows = vec![header];
+    let hours = |value: &f64| format!("{value:.2}");
+    for row in &sheet.rows {
+        let mut cells = vec![row.group.clone()];
+        cells.extend(row.hours.iter().map(hours));
+        cells.push(hours(&row.total));
+        rows.push(cells);
+    }
+    let mut totals = vec!["Total".to_string()];
+    totals.extend(sheet.totals.iter().map(hours));
+    totals.push(hours(&sheet.total));
+    rows.push(totals);
+    rows
+}
+
+/// Left-aligned columns separated by two spaces, the first row being the header.
+fn render_table(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let mut out = String::new();
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        out.push_str(cells.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn write_csv(rows: &[Vec<String>]) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(io::stdout());
+    for row in rows {
+        writer.write_record(row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use jico::JiraClient;
+use jico::standup::{Activity, OpenWork, Standup, parse_since};
+
+pub async fn run_standup(client: &JiraClient, user: &str, since: &str) -> Result<()> {
+    let since = parse_since(since, &Local::now())?;
+    let standup = client.standup(user, since).await?;
+    print!("{}", markdown(&standup));
+    Ok(())
+}
+
+/// The standup as Markdown, ready to paste into chat.
+fn markdown(standup: &Standup) -> String {
+    let since = DateTime::parse_from_rfc3339(&standup.since)
+        .map(|at| {
+            at.with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|_| standup.since.clone());
+    let name = match standup.user.display_name.as_str() {
+        "" => &standup.user.account_id,
+        name => name,
+    };
+    let mut out = format!("## Standup: {name} (since {since})\n");
+    section(&mut out, "Worked on", standup.activity.iter().map(activity));
+    section(
+        &mut out,
+        "In progress",
+        standup.in_progress.iter().map(work),
+    );
+    section(&mut out, "Blocked", standup.blocked.iter().map(work));
+    out
+}
+
+fn section(out: &mut String, title: &str, bullets: impl Iterator<Item = String>) {
+    out.push_str(&format!("\n**{title}**\n"));
+    let start = out.len();
+    for bullet in bullets {
+        out.push_str(&format!("- {bullet}\n"));
+    }
+    if out.len() == start {
+        out.push_str("- _Nothing_\n");
+    }
+}
+
+/// `PROJ-1 Login — moved To Do → In Progress; commented 2×; logged 1h 30m`
+fn activity(activity: &Activity) -> String {
+    let mut done = Vec::new();
+    if !activity.transitions.is_empty() {
+        done.push(format!("moved {}", activity.transitions.join(", ")));
+    }
+    if activity.comments > 0 {
+        done.push(format!("commented {}×", activity.comments));
+    }
+    if activity.logged_seconds > 0 {
+        done.push(format!("logged {}", duration(activity.logged_seconds)));
+    }
+    format!(
+        "{} {} — {}",
+        activity.key,
+        activity.summary,
+        done.join("; ")
+    )
+}
+
+/// `PROJ-2 Search (In Progress)`, plus what blocks it.
+fn work(work: &OpenWork) -> String {
+    let line = format!("{} {} ({})", work.key, work.summary, work.status);
+    if work.blocked_by.is_empty() {
+        return line;
+    }
+    format!("{line} — blocked by {}", work.blocked_by.join(", "))
+}
+
+/// `1h 30m`, `45m` or `2h`.
+fn duration(seconds: i64) -> String {
+    let minutes = (seconds + 30) / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{m}m"),
+        (h, 0) => format!("{h}h"),
+        (h, m) => format!("{h}h {m}m"),
+    }
+}
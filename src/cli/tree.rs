@@ -0,0 +1,69 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use jico::{IssueTree, JiraClient, TreeOptions};
+
+use super::print_json;
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum TreeFormat {
+    /// Indented tree with status and assignee
+    #[default]
+    Text,
+    /// The nested structure as JSON
+    Json,
+}
+
+pub async fn run_tree(
+    client: &JiraClient,
+    key: &str,
+    options: TreeOptions,
+    format: TreeFormat,
+) -> Result<()> {
+    let tree = client.issue_tree(key, options).await?;
+    match format {
+        TreeFormat::Text => print!("{}", render(&tree)),
+        TreeFormat::Json => print_json(&tree),
+    }
+    Ok(())
+}
+
+fn render(tree: &IssueTree) -> String {
+    let mut out = format!("{}\n", describe(tree));
+    render_children(tree, "", &mut out);
+    out
+}
+
+fn render_children(tree: &IssueTree, prefix: &str, out: &mut String) {
+    for (i, child) in tree.children.iter().enumerate() {
+        let last = i + 1 == tree.children.len();
+        let (branch, indent) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        out.push_str(&format!("{prefix}{branch}{}\n", describe(child)));
+        render_children(child, &format!("{prefix}{indent}"), out);
+    }
+}
+
+/// `blocks ACME-9 [Task] Summary (In Progress, Ada)`
+fn describe(node: &IssueTree) -> String {
+    let mut line = String::new();
+    if let Some(link) = &node.link {
+        line.push_str(&format!("{link} "));
+    }
+    line.push_str(&node.key);
+    if let Some(issue_type) = &node.issue_type {
+        line.push_str(&format!(" [{issue_type}]"));
+    }
+    if let Some(summary) = &node.summary {
+        line.push_str(&format!(" {summary}"));
+    }
+    let status = node.status.as_deref().unwrap_or("Unknown");
+    let assignee = node.assignee.as_deref().unwrap_or("unassigned");
+    line.push_str(&format!(" ({status}, {assignee})"));
+    if node.repeated {
+        line.push_str(" (see above)");
+    }
+    line
+}
@@ -0,0 +1,1286 @@
+use anyhow::{Context, Result, anyhow};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use reqwest::{Method, StatusCode, header};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value, json};
+
+use crate::adf::description_to_adf;
+use crate::error::{ErrorKind, JiraError};
+use crate::journal::{Change, Journal};
+use crate::link::LinkRelation;
+use crate::models::{Comment, Issue, SearchResults, Transition, User};
+use crate::retry::RetryPolicy;
+use crate::settings::Settings;
+
+/// Which Jira REST API a [`Request`] path is relative to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Api {
+    /// The platform API, `/rest/api/3`.
+    #[default]
+    Platform,
+    /// The Jira Software API for boards and sprints, `/rest/agile/1.0`.
+    Agile,
+}
+
+impl Api {
+    fn base_path(self) -> &'static str {
+        match self {
+            Api::Platform => "rest/api/3",
+            Api::Agile => "rest/agile/1.0",
+        }
+    }
+}
+
+/// What a [`Request`] sends.
+#[derive(Debug, Clone)]
+enum Body {
+    Json(Value),
+    /// A single `file` part of a `multipart/form-data` upload.
+    File {
+        filename: String,
+        mime_type: String,
+        content: Vec<u8>,
+    },
+}
+
+/// One call against a Jira REST API, run by [`JiraClient::execute`].
+#[derive(Debug, Clone)]
+pub struct Request {
+    method: Method,
+    api: Api,
+    path: String,
+    query: Vec<(String, String)>,
+    body: Option<Body>,
+    accept: &'static str,
+    idempotent: bool,
+    read_only: bool,
+    action: &'static str,
+}
+
+impl Request {
+    pub fn new(method: Method, path: impl Into<String>, body: Option<Value>) -> Self {
+        // GET, PUT and DELETE are idempotent by definition; POSTs opt in via `idempotent()`.
+        let idempotent = method != Method::POST;
+        Self {
+            read_only: method == Method::GET,
+            method,
+            api: Api::Platform,
+            path: path.into(),
+            query: Vec::new(),
+            body: body.map(Body::Json),
+            accept: "application/json",
+            idempotent,
+            action: "Jira",
+        }
+    }
+
+    pub fn get(path: impl Into<String>) -> Self {
+        Self::new(Method::GET, path, None)
+    }
+
+    pub fn post(path: impl Into<String>, body: Value) -> Self {
+        Self::new(Method::POST, path, Some(body))
+    }
+
+    pub fn put(path: impl Into<String>, body: Value) -> Self {
+        Self::new(Method::PUT, path, Some(body))
+    }
+
+    /// POSTs `content` as the `file` part of a multipart upload.
+    pub fn upload(
+        path: impl Into<String>,
+        filename: &str,
+        mime_type: &str,
+        content: Vec<u8>,
+    ) -> Self {
+        Self {
+            body: Some(Body::File {
+                filename: filename.to_string(),
+                mime_type: mime_type.to_string(),
+                content,
+            }),
+            ..Self::new(Method::POST, path, None)
+        }
+    }
+
+    /// Resolves the path against the agile API instead of the platform API.
+    pub fn agile(mut self) -> Self {
+        self.api = Api::Agile;
+        self
+    }
+
+    pub fn query(mut self, key: &str, value: impl ToString) -> Self {
+        self.query.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Marks a POST that only reads (such as a search) as safe to retry.
+    pub fn idempotent(mut self) -> Self {
+        self.idempotent = true;
+        self
+    }
+
+    /// Marks a POST that changes nothing (such as a search): it is retried like
+    /// an idempotent request and still sent under [`JiraClient::dry_run`].
+    pub fn read_only(mut self) -> Self {
+        self.idempotent = true;
+        self.read_only = true;
+        self
+    }
+
+    /// Marks a GET whose body is only sent back to Jira (such as attachment
+    /// content): under [`JiraClient::dry_run`] it is printed, not sent.
+    pub fn for_write(mut self) -> Self {
+        self.read_only = false;
+        self
+    }
+
+    /// Accepts a body other than JSON, such as attachment content.
+    pub fn accept(mut self, media_type: &'static str) -> Self {
+        self.accept = media_type;
+        self
+    }
+
+    /// Names the call in error messages ("Failed to send <action> request").
+    pub fn action(mut self, action: &'static str) -> Self {
+        self.action = action;
+        self
+    }
+}
+
+const SEARCH_PAGE_SIZE: u32 = 100;
+
+/// Keys per `... in (...)` clause, keeping JQL well below Jira's length limits.
+const KEYS_PER_QUERY: usize = 50;
+
+/// Fields for a new issue; `None` values are left out of the request.
+#[derive(Debug, Clone, Default)]
+pub struct NewIssue {
+    pub project_key: String,
+    pub summary: String,
+    pub issue_type: String,
+    pub description: Option<String>,
+    pub parent: Option<String>,
+    pub labels: Option<Vec<String>>,
+    pub priority: Option<String>,
+    pub assignee: Option<String>,
+}
+
+impl NewIssue {
+    pub fn new(project_key: &str, summary: &str, issue_type: &str) -> Self {
+        Self {
+            project_key: project_key.to_string(),
+            summary: summary.to_string(),
+            issue_type: issue_type.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// The `fields` object Jira expects when creating this issue.
+    pub fn fields(&self) -> Map<String, Value> {
+        let mut fields = Map::new();
+        fields.insert("project".to_string(), json!({ "key": self.project_key }));
+        fields.insert("summary".to_string(), json!(self.summary));
+        fields.insert("issuetype".to_string(), json!({ "name": self.issue_type }));
+        let description_adf = self
+            .description
+            .as_deref()
+            .map(description_to_adf)
+            .unwrap_or_else(|| json!(null));
+        fields.insert("description".to_string(), description_adf);
+        if let Some(parent) = &self.parent {
+            fields.insert("parent".to_string(), json!({ "key": parent }));
+        }
+        if let Some(labels) = &self.labels {
+            fields.insert("labels".to_string(), json!(labels));
+        }
+        if let Some(priority) = &self.priority {
+            fields.insert("priority".to_string(), json!({ "name": priority }));
+        }
+        if let Some(assignee) = &self.assignee {
+            fields.insert("assignee".to_string(), json!({ "accountId": assignee }));
+        }
+        fields
+    }
+}
+
+/// Reduces a field value read from Jira to what create and update requests accept: objects
+/// such as users, priorities and options are sent back by id.
+pub(crate) fn writable(value: &Value) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(items.iter().map(writable).collect()),
+        Value::Object(object) => {
+            for id in ["accountId", "id"] {
+                if let Some(found) = object.get(id) {
+                    return json!({ id: found });
+                }
+            }
+            value.clone()
+        }
+        _ => value.clone(),
+    }
+}
+
+/// Async client for the Jira Cloud REST API.
+pub struct JiraClient {
+    base_url: String,
+    http: reqwest::Client,
+    pub retry: RetryPolicy,
+    /// Print mutating requests (method, URL and body) to stderr instead of
+    /// sending them; they behave as if Jira answered 204 No Content.
+    pub dry_run: bool,
+    /// Where updates, transitions and links are recorded for `undo`; off when `None`.
+    pub journal: Option<Journal>,
+    story_points_field: Option<String>,
+}
+
+impl JiraClient {
+    pub fn new(settings: &Settings) -> Result<Self> {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            header::ACCEPT,
+            header::HeaderValue::from_static("application/json"),
+        );
+        let auth = header::HeaderValue::from_str(&format!(
+            "Basic {}",
+            STANDARD.encode(format!("{}:{}", settings.email, settings.api_token))
+        ))
+        .context("Failed to encode auth header")?;
+        headers.insert(header::AUTHORIZATION, auth);
+
+        let http = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .context("Failed to build HTTP client")?;
+
+        Ok(Self {
+            base_url: settings.base_url.clone(),
+            http,
+            retry: RetryPolicy::new(settings.max_attempts),
+            dry_run: false,
+            journal: None,
+            story_points_field: settings.story_points_field.clone(),
+        })
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Sends a request, retrying throttled and gateway failures when `idempotent` is set.
+    /// Non-idempotent requests (e.g. creating issues or links) are sent exactly once.
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+        idempotent: bool,
+    ) -> reqwest::Result<reqwest::Response> {
+        let mut attempt = 1;
+        loop {
+            let retry = if idempotent && attempt < self.retry.max_attempts {
+                request.try_clone()
+            } else {
+                None
+            };
+            let Some(next) = retry else {
+                return request.send().await;
+            };
+            let resp = next.send().await?;
+            if !RetryPolicy::should_retry(resp.status()) {
+                return Ok(resp);
+            }
+            tokio::time::sleep(self.retry.delay(attempt, resp.headers())).await;
+            attempt += 1;
+        }
+    }
+
+    /// Runs a [`Request`] and returns its JSON body; empty bodies (e.g. 204 No Content) become `{}`.
+    pub async fn execute(&self, request: Request) -> Result<Value> {
+        let body = self.execute_raw(&request).await?;
+        if body.iter().all(u8::is_ascii_whitespace) {
+            return Ok(json!({}));
+        }
+        serde_json::from_slice(&body)
+            .with_context(|| format!("Failed to parse {} response", request.action))
+    }
+
+    /// Runs a [`Request`] and returns its body as is.
+    pub async fn execute_raw(&self, request: &Request) -> Result<Vec<u8>> {
+        let (status, body) = self.fetch(request).await?;
+        if !status.is_success() {
+            return Err(JiraError::from_body(status, &body).into());
+        }
+        Ok(body)
+    }
+
+    /// Sends a [`Request`] and reads the raw body, whatever the status.
+    pub(crate) async fn fetch(&self, request: &Request) -> Result<(StatusCode, Vec<u8>)> {
+        let url = format!(
+            "{}/{}/{}",
+            self.base_url,
+            request.api.base_path(),
+            request.path
+        );
+        if self.dry_run && !request.read_only {
+            let shown = if request.query.is_empty() {
+                url
+            } else {
+                reqwest::Url::parse_with_params(&url, &request.query)
+                    .map(String::from)
+                    .unwrap_or(url)
+            };
+            eprintln!("DRY RUN: {} {shown}", request.method);
+            match &request.body {
+                Some(Body::Json(body)) => eprintln!("{}", serde_json::to_string_pretty(body)?),
+                Some(Body::File {
+                    filename,
+                    mime_type,
+                    ..
+                }) => eprintln!("(file {filename}, {mime_type})"),
+                None => {}
+            }
+            return Ok((StatusCode::NO_CONTENT, Vec::new()));
+        }
+        let mut builder = self
+            .http
+            .request(request.method.clone(), url)
+            .header(header::ACCEPT, request.accept);
+        if !request.query.is_empty() {
+            builder = builder.query(&request.query);
+        }
+        match &request.body {
+            Some(Body::Json(body)) => builder = builder.json(body),
+            Some(Body::File {
+                filename,
+                mime_type,
+                content,
+            }) => {
+                // Built by hand: Jira only needs the one `file` part.
+                let boundary = format!("jico-{:016x}", fastrand::u64(..));
+                let mut body = format!(
+                    "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: {mime_type}\r\n\r\n",
+                    filename.replace(['"', '\r', '\n'], "_")
+                )
+                .into_bytes();
+                body.extend_from_slice(content);
+                body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+                builder = builder
+                    .header("X-Atlassian-Token", "no-check")
+                    .header(
+                        header::CONTENT_TYPE,
+                        format!("multipart/form-data; boundary={boundary}"),
+                    )
+                    .body(body);
+            }
+            None => {}
+        }
+        let resp = self
+            .send(builder, request.idempotent)
+            .await
+            .with_context(|| format!("Failed to send {} request", request.action))?;
+        let status = resp.status();
+        let body = resp
+            .bytes()
+            .await
+            .with_context(|| format!("Failed to read {} response", request.action))?;
+        Ok((status, body.to_vec()))
+    }
+
+    /// Like [`JiraClient::execute`], deserializing the body into `T`.
+    pub async fn execute_as<T: DeserializeOwned>(&self, request: Request) -> Result<T> {
+        let action = request.action;
+        let value = self.execute(request).await?;
+        serde_json::from_value(value).with_context(|| format!("Failed to parse {action} response"))
+    }
+
+    pub async fn create_issue(&self, issue: &NewIssue) -> Result<Issue> {
+        let body = json!({ "fields": issue.fields() });
+        self.execute_as(Request::post("issue", body).action("create issue"))
+            .await
+    }
+
+    pub async fn list_issues(&self, jql: &str, limit: u32) -> Result<SearchResults> {
+        self.search(jql, &[], limit, None).await
+    }
+
+    /// Fetches one page of JQL results; `fields` empty means Jira's default (ids only).
+    pub async fn search(
+        &self,
+        jql: &str,
+        fields: &[&str],
+        limit: u32,
+        next_page_token: Option<&str>,
+    ) -> Result<SearchResults> {
+        // Atlassian migrated search to /search/jql; body still uses "jql".
+        let mut body = json!({
+            "jql": jql,
+            "maxResults": limit,
+        });
+        if !fields.is_empty() {
+            body["fields"] = json!(fields);
+        }
+        if let Some(token) = next_page_token {
+            body["nextPageToken"] = json!(token);
+        }
+        self.execute_as(
+            Request::post("search/jql", body)
+                .read_only()
+                .action("search"),
+        )
+        .await
+    }
+
+    /// Follows `nextPageToken` until every issue matching `jql` is fetched.
+    pub async fn search_all(&self, jql: &str, fields: &[&str]) -> Result<Vec<Issue>> {
+        let mut issues = Vec::new();
+        let mut token: Option<String> = None;
+        loop {
+            let page = self
+                .search(jql, fields, SEARCH_PAGE_SIZE, token.as_deref())
+                .await?;
+            issues.extend(page.issues);
+            match page.next_page_token {
+                Some(next) if page.is_last != Some(true) => token = Some(next),
+                _ => return Ok(issues),
+            }
+        }
+    }
+
+    /// All issues whose `field` (`key`, `parent`, ...) is one of `keys`, searched in chunks.
+    pub(crate) async fn search_keys(
+        &self,
+        field: &str,
+        keys: &[String],
+        fields: &[&str],
+    ) -> Result<Vec<Issue>> {
+        let mut issues = Vec::new();
+        for chunk in keys.chunks(KEYS_PER_QUERY) {
+            let jql = format!("{field} in ({}) ORDER BY key ASC", chunk.join(", "));
+            issues.extend(self.search_all(&jql, fields).await?);
+        }
+        Ok(issues)
+    }
+
+    pub async fn get_issue(&self, key: &str) -> Result<Issue> {
+        self.execute_as(Request::get(format!("issue/{key}")).action("get issue"))
+            .await
+    }
+
+    pub async fn get_issue_subtasks(&self, key: &str) -> Result<Vec<Issue>> {
+        let issue: Issue = self
+            .execute_as(
+                Request::get(format!("issue/{key}"))
+                    .query("fields", "subtasks")
+                    .action("get issue subtasks"),
+            )
+            .await?;
+        Ok(issue.fields.subtasks.unwrap_or_default())
+    }
+
+    pub async fn update_issue(&self, key: &str, fields: Map<String, Value>) -> Result<Value> {
+        let names: Vec<&str> = fields.keys().map(String::as_str).collect();
+        let before = self.snapshot_fields(key, &names).await?;
+        let body = json!({ "fields": fields });
+        let result = self
+            .execute(Request::put(format!("issue/{key}"), body).action("update issue"))
+            .await?;
+        self.record(before)?;
+        Ok(result)
+    }
+
+    /// Deletes `key`; Jira refuses issues with sub-tasks unless `delete_subtasks` is set.
+    pub async fn delete_issue(&self, key: &str, delete_subtasks: bool) -> Result<()> {
+        self.execute(
+            Request::new(Method::DELETE, format!("issue/{key}"), None)
+                .query("deleteSubtasks", delete_subtasks)
+                .action("delete issue"),
+        )
+        .await?;
+        self.record(Some(Change::Irreversible {
+            key: key.to_string(),
+            action: "delete".to_string(),
+        }))
+    }
+
+    pub async fn get_transitions(&self, key: &str) -> Result<Vec<Transition>> {
+        let payload = self
+            .execute(Request::get(format!("issue/{key}/transitions")).action("get transitions"))
+            .await?;
+        let transitions = payload
+            .get("transitions")
+            .cloned()
+            .ok_or_else(|| anyhow!("No transitions found in response"))?;
+        serde_json::from_value(transitions).context("Failed to parse get transitions response")
+    }
+
+    pub async fn transition_issue(&self, key: &str, target: &str) -> Result<Value> {
+        let transitions = self.get_transitions(key).await?;
+        let transition = transitions
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(target))
+            .ok_or_else(|| {
+                JiraError::new(
+                    ErrorKind::Validation,
+                    format!("Transition '{}' not available for {}", target, key),
+                )
+            })?;
+        self.run_transition(key, transition, None).await
+    }
+
+    /// Performs `transition` on `key`, setting `fields` on its screen (e.g. a resolution).
+    pub(crate) async fn run_transition(
+        &self,
+        key: &str,
+        transition: &Transition,
+        fields: Option<Map<String, Value>>,
+    ) -> Result<Value> {
+        let before = self.snapshot_status(key, &transition.name).await?;
+        let mut body = json!({"transition": { "id": transition.id }});
+        if let Some(fields) = fields {
+            body["fields"] = Value::Object(fields);
+        }
+        let result = self
+            .execute(Request::post(format!("issue/{key}/transitions"), body).action("transition"))
+            .await?;
+        self.record(before)?;
+        Ok(result)
+    }
+
+    pub async fn link_issues(&self, key: &str, to: &str, relation: LinkRelation) -> Result<Value> {
+        let (outward_key, inward_key) = relation.outward_inward_keys(key, to);
+        let body = json!({
+            "type": { "name": relation.link_type_name() },
+            "outwardIssue": { "key": outward_key },
+            "inwardIssue": { "key": inward_key }
+        });
+        // Linking twice is a no-op in Jira, so only a link that did not exist is journaled.
+        let created = self.journaling() && self.link_id(key, to, relation).await?.is_none();
+        let result = self
+            .execute(Request::post("issueLink", body).action("issue link"))
+            .await?;
+        self.record(created.then(|| Change::Link {
+            key: key.to_string(),
+            to: to.to_string(),
+            relation,
+        }))?;
+        Ok(result)
+    }
+
+    /// Removes the `relation` link between `key` and `to`.
+    pub async fn unlink_issues(&self, key: &str, to: &str, relation: LinkRelation) -> Result<()> {
+        let id = self.link_id(key, to, relation).await?.ok_or_else(|| {
+            JiraError::new(
+                ErrorKind::NotFound,
+                format!("{key} has no '{}' link to {to}", relation.link_type_name()),
+            )
+        })?;
+        self.execute(
+            Request::new(Method::DELETE, format!("issueLink/{id}"), None)
+                .action("delete issue link"),
+        )
+        .await?;
+        self.record(Some(Change::Unlink {
+            key: key.to_string(),
+            to: to.to_string(),
+            relation,
+        }))
+    }
+
+    /// Id of the link [`JiraClient::link_issues`] would create, if it exists.
+    async fn link_id(&self, key: &str, to: &str, relation: LinkRelation) -> Result<Option<String>> {
+        let issue: Issue = self
+            .execute_as(
+                Request::get(format!("issue/{key}"))
+                    .query("fields", "issuelinks")
+                    .action("get issue links"),
+            )
+            .await?;
+        // Seen from `key`, the other issue sits on the same side it was posted on.
+        let to_is_outward = relation.outward_inward_keys(key, to).0 == to;
+        Ok(issue
+            .fields
+            .issuelinks
+            .unwrap_or_default()
+            .into_iter()
+            .find(|link| {
+                let other = if to_is_outward {
+                    &link.outward_issue
+                } else {
+                    &link.inward_issue
+                };
+                link.link_type
+                    .name
+                    .eq_ignore_ascii_case(relation.link_type_name())
+                    && other.as_ref().is_some_and(|issue| issue.key == to)
+            })
+            .and_then(|link| link.id))
+    }
+
+    /// Downloads the content of attachment `id`; empty under [`JiraClient::dry_run`],
+    /// where it would only be uploaded again.
+    pub async fn download_attachment(&self, id: &str) -> Result<Vec<u8>> {
+        self.execute_raw(
+            &Request::get(format!("attachment/content/{id}"))
+                .accept("*/*")
+                .for_write()
+                .action("download attachment"),
+        )
+        .await
+    }
+
+    /// Attaches a file to `key`.
+    pub async fn add_attachment(
+        &self,
+        key: &str,
+        filename: &str,
+        mime_type: &str,
+        content: Vec<u8>,
+    ) -> Result<Value> {
+        self.execute(
+            Request::upload(
+                format!("issue/{key}/attachments"),
+                filename,
+                mime_type,
+                content,
+            )
+            .action("add attachment"),
+        )
+        .await
+    }
+
+    pub async fn get_comments(&self, key: &str) -> Result<Vec<Comment>> {
+        let payload = self
+            .execute(Request::get(format!("issue/{key}/comment")).action("get comments"))
+            .await?;
+        let comments = payload
+            .get("comments")
+            .cloned()
+            .unwrap_or_else(|| json!([]));
+        serde_json::from_value(comments).context("Failed to parse get comments response")
+    }
+
+    /// Ids of the story points fields: the configured one, or else the fields named
+    /// "Story Points" (company-managed) and "Story point estimate" (team-managed).
+    pub async fn story_point_fields(&self) -> Result<Vec<String>> {
+        if let Some(field) = &self.story_points_field {
+            return Ok(vec![field.clone()]);
+        }
+        let fields = self
+            .execute(Request::get("field").action("list fields"))
+            .await?;
+        Ok(fields
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .filter(|field| {
+                field
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .is_some_and(|name| {
+                        name.eq_ignore_ascii_case("Story Points")
+                            || name.eq_ignore_ascii_case("Story point estimate")
+                    })
+            })
+            .filter_map(|field| field.get("id").and_then(Value::as_str).map(str::to_string))
+            .collect())
+    }
+
+    pub async fn myself(&self) -> Result<User> {
+        self.execute_as(Request::get("myself").action("myself"))
+            .await
+    }
+
+    /// The user `query` names: `me`, an email address, a display name or an account id.
+    pub async fn find_user(&self, query: &str) -> Result<User> {
+        if query.eq_ignore_ascii_case("me") {
+            return self.myself().await;
+        }
+        let search = |param: &str| {
+            self.execute_as::<Vec<User>>(
+                Request::get("user/search")
+                    .query(param, query)
+                    .action("search users"),
+            )
+        };
+        let mut users = search("query").await?;
+        if users.is_empty() {
+            users = search("accountId").await?;
+        }
+        let exact = users.iter().position(|user| {
+            user.account_id == query
+                || user.display_name.eq_ignore_ascii_case(query)
+                || user
+                    .email_address
+                    .as_deref()
+                    .is_some_and(|email| email.eq_ignore_ascii_case(query))
+        });
+        match (exact, users.len()) {
+            (Some(index), _) => Ok(users.swap_remove(index)),
+            (None, 1) => Ok(users.remove(0)),
+            (None, 0) => Err(JiraError::new(
+                ErrorKind::NotFound,
+                format!("No user matches '{query}'"),
+            )
+            .into()),
+            (None, _) => {
+                let names: Vec<&str> = users.iter().map(|u| u.display_name.as_str()).collect();
+                Err(JiraError::new(
+                    ErrorKind::Validation,
+                    format!("'{query}' matches several users: {}", names.join(", ")),
+                )
+                .into())
+            }
+        }
+    }
+
+    /// Runs `request` and hands back the raw status instead of failing on it,
+    /// so `doctor` can explain what went wrong.
+    pub async fn probe(&self, request: &Request) -> Result<(StatusCode, Value)> {
+        let (status, body) = self.fetch(request).await?;
+        let value = serde_json::from_slice(&body).unwrap_or_else(|_| json!({}));
+        Ok((status, value))
+    }
+}
+
+/// A client for a mock server that tries three times without waiting in between.
+#[cfg(test)]
+pub(crate) fn test_client(server: &httpmock::MockServer) -> JiraClient {
+    let mut client = JiraClient::new(&Settings {
+        max_attempts: 3,
+        ..Settings::new(&server.base_url(), "user@example.com", "token")
+    })
+    .unwrap();
+    client.retry.base_delay = std::time::Duration::ZERO;
+    client
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+
+    #[test]
+    fn writable_sends_objects_back_by_id() {
+        let value = json!([
+            { "accountId": "abc", "displayName": "Ann" },
+            { "id": "3", "name": "High", "iconUrl": "x" },
+            { "type": "doc", "version": 1, "content": [] },
+        ]);
+        assert_eq!(
+            writable(&value),
+            json!([
+                { "accountId": "abc" },
+                { "id": "3" },
+                { "type": "doc", "version": 1, "content": [] },
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn create_issue_sends_all_fields() {
+        let server = MockServer::start();
+        let expected_body = json!({
+            "fields": {
+                "project": { "key": "ACME" },
+                "summary": "Title",
+                "issuetype": { "name": "Task" },
+                "description": description_to_adf("Desc"),
+                "labels": ["bug", "ui"],
+                "priority": { "name": "High" },
+                "assignee": { "accountId": "abc" }
+            }
+        });
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/issue")
+                .json_body(expected_body.clone());
+            then.status(201).json_body(json!({ "id": "10000" }));
+        });
+
+        let client = test_client(&server);
+        let issue = NewIssue {
+            description: Some("Desc".to_string()),
+            labels: Some(vec!["bug".to_string(), "ui".to_string()]),
+            priority: Some("High".to_string()),
+            assignee: Some("abc".to_string()),
+            ..NewIssue::new("ACME", "Title", "Task")
+        };
+        let response = client.create_issue(&issue).await.unwrap();
+
+        mock.assert();
+        assert_eq!(response.id, "10000");
+    }
+
+    #[tokio::test]
+    async fn create_issue_with_parent_sets_parent_field() {
+        let server = MockServer::start();
+        let expected_body = json!({
+            "fields": {
+                "project": { "key": "ACME" },
+                "summary": "Child issue",
+                "issuetype": { "name": "Sub-task" },
+                "description": null,
+                "parent": { "key": "ACME-1" }
+            }
+        });
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/issue")
+                .json_body(expected_body.clone());
+            then.status(201).json_body(json!({ "id": "10001" }));
+        });
+
+        let client = test_client(&server);
+        let issue = NewIssue {
+            parent: Some("ACME-1".to_string()),
+            ..NewIssue::new("ACME", "Child issue", "Sub-task")
+        };
+        let response = client.create_issue(&issue).await.unwrap();
+
+        mock.assert();
+        assert_eq!(response.id, "10001");
+    }
+
+    #[tokio::test]
+    async fn get_issue_subtasks_returns_list() {
+        let server = MockServer::start();
+        let response_body = json!({
+            "fields": {
+                "subtasks": [
+                    { "id": "20001", "key": "ACME-2" },
+                    { "id": "20002", "key": "ACME-3" }
+                ]
+            }
+        });
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/api/3/issue/ACME-1")
+                .query_param("fields", "subtasks");
+            then.status(200).json_body(response_body.clone());
+        });
+
+        let client = test_client(&server);
+        let response = client.get_issue_subtasks("ACME-1").await.unwrap();
+
+        mock.assert();
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            response_body["fields"]["subtasks"]
+        );
+    }
+
+    #[tokio::test]
+    async fn update_issue_sends_requested_fields() {
+        let server = MockServer::start();
+        let expected_body = json!({
+            "fields": {
+                "summary": "New summary",
+                "labels": ["backend"],
+                "priority": { "name": "Medium" },
+                "assignee": { "accountId": "xyz" }
+            }
+        });
+        let mock = server.mock(|when, then| {
+            when.method(PUT)
+                .path("/rest/api/3/issue/ACME-1")
+                .json_body(expected_body.clone());
+            then.status(200).json_body(json!({ "ok": true }));
+        });
+
+        let client = test_client(&server);
+        let mut fields = Map::new();
+        fields.insert("summary".to_string(), json!("New summary"));
+        fields.insert("labels".to_string(), json!(["backend"]));
+        fields.insert("priority".to_string(), json!({ "name": "Medium" }));
+        fields.insert("assignee".to_string(), json!({ "accountId": "xyz" }));
+
+        let response = client.update_issue("ACME-1", fields).await.unwrap();
+
+        mock.assert();
+        assert_eq!(response["ok"], true);
+    }
+
+    #[tokio::test]
+    async fn update_issue_allows_empty_response() {
+        let server = MockServer::start();
+        let expected_body = json!({
+            "fields": {
+                "summary": "Another summary"
+            }
+        });
+        let mock = server.mock(|when, then| {
+            when.method(PUT)
+                .path("/rest/api/3/issue/ACME-2")
+                .json_body(expected_body.clone());
+            then.status(204);
+        });
+
+        let client = test_client(&server);
+        let mut fields = Map::new();
+        fields.insert("summary".to_string(), json!("Another summary"));
+
+        let response = client.update_issue("ACME-2", fields).await.unwrap();
+
+        mock.assert();
+        assert_eq!(response, json!({}));
+    }
+
+    #[tokio::test]
+    async fn link_issues_blocks_sets_outward_as_target_issue() {
+        let server = MockServer::start();
+        let expected_body = json!({
+            "type": { "name": "Blocks" },
+            "outwardIssue": { "key": "MG-26" },
+            "inwardIssue": { "key": "MG-3" }
+        });
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/issueLink")
+                .json_body(expected_body.clone());
+            then.status(201);
+        });
+
+        let client = test_client(&server);
+        let response = client
+            .link_issues("MG-3", "MG-26", LinkRelation::Blocks)
+            .await
+            .unwrap();
+
+        mock.assert();
+        assert_eq!(response, json!({}));
+    }
+
+    #[tokio::test]
+    async fn link_issues_blocked_by_sets_outward_as_blocker_issue() {
+        let server = MockServer::start();
+        let expected_body = json!({
+            "type": { "name": "Blocks" },
+            "outwardIssue": { "key": "MG-26" },
+            "inwardIssue": { "key": "MG-3" }
+        });
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/issueLink")
+                .json_body(expected_body.clone());
+            then.status(201).json_body(json!({ "ok": true }));
+        });
+
+        let client = test_client(&server);
+        let response = client
+            .link_issues("MG-26", "MG-3", LinkRelation::BlockedBy)
+            .await
+            .unwrap();
+
+        mock.assert();
+        assert_eq!(response["ok"], true);
+    }
+
+    #[tokio::test]
+    async fn search_all_follows_next_page_token() {
+        let server = MockServer::start();
+        let first = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/search/jql")
+                .json_body(json!({
+                    "jql": "parent = ACME-1",
+                    "maxResults": 100,
+                    "fields": ["summary"]
+                }));
+            then.status(200).json_body(json!({
+                "issues": [{ "id": "1", "key": "ACME-2" }],
+                "nextPageToken": "page-2"
+            }));
+        });
+        let second = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/search/jql")
+                .json_body(json!({
+                    "jql": "parent = ACME-1",
+                    "maxResults": 100,
+                    "fields": ["summary"],
+                    "nextPageToken": "page-2"
+                }));
+            then.status(200).json_body(json!({
+                "issues": [{ "id": "2", "key": "ACME-3" }],
+                "isLast": true
+            }));
+        });
+
+        let client = test_client(&server);
+        let issues = client
+            .search_all("parent = ACME-1", &["summary"])
+            .await
+            .unwrap();
+
+        first.assert();
+        second.assert();
+        let keys: Vec<_> = issues.iter().map(|i| i.key.as_str()).collect();
+        assert_eq!(keys, ["ACME-2", "ACME-3"]);
+    }
+
+    #[tokio::test]
+    async fn story_point_fields_are_discovered_by_name() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/field");
+            then.status(200).json_body(json!([
+                { "id": "summary", "name": "Summary" },
+                { "id": "customfield_10016", "name": "Story point estimate" },
+                { "id": "customfield_10028", "name": "Story Points" }
+            ]));
+        });
+
+        let client = test_client(&server);
+        let fields = client.story_point_fields().await.unwrap();
+
+        assert_eq!(fields, ["customfield_10016", "customfield_10028"]);
+    }
+
+    #[tokio::test]
+    async fn myself_returns_account() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/myself");
+            then.status(200)
+                .json_body(json!({ "accountId": "abc", "displayName": "Ada" }));
+        });
+
+        let client = test_client(&server);
+        let response = client.myself().await.unwrap();
+
+        mock.assert();
+        assert_eq!(response.account_id, "abc");
+    }
+
+    #[tokio::test]
+    async fn probe_returns_error_status_without_failing() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/project/NOPE");
+            then.status(404)
+                .json_body(json!({ "errorMessages": ["No project could be found"] }));
+        });
+
+        let client = test_client(&server);
+        let (status, body) = client.probe(&Request::get("project/NOPE")).await.unwrap();
+
+        mock.assert();
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["errorMessages"][0], "No project could be found");
+    }
+
+    #[tokio::test]
+    async fn get_issue_retries_unavailable_up_to_max_attempts() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-1");
+            then.status(503)
+                .json_body(json!({ "errorMessages": ["Try later"] }));
+        });
+
+        let client = test_client(&server);
+        let err = client.get_issue("ACME-1").await.unwrap_err();
+
+        mock.assert_hits(3);
+        assert!(err.to_string().contains("503"));
+    }
+
+    #[tokio::test]
+    async fn search_retries_rate_limited_requests_honoring_retry_after() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/rest/api/3/search/jql");
+            then.status(429)
+                .header("Retry-After", "0")
+                .json_body(json!({ "errorMessages": ["Rate limited"] }));
+        });
+
+        let client = test_client(&server);
+        let err = client.list_issues("project = ACME", 10).await.unwrap_err();
+
+        mock.assert_hits(3);
+        assert!(err.to_string().contains("429"));
+    }
+
+    #[tokio::test]
+    async fn create_issue_is_not_retried() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/rest/api/3/issue");
+            then.status(503)
+                .json_body(json!({ "errorMessages": ["Try later"] }));
+        });
+
+        let client = test_client(&server);
+        let result = client
+            .create_issue(&NewIssue::new("ACME", "Title", "Task"))
+            .await;
+
+        mock.assert_hits(1);
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn update_issue_surfaces_field_errors() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(PUT).path("/rest/api/3/issue/ACME-1");
+            then.status(400).json_body(json!({
+                "errorMessages": [],
+                "errors": { "priority": "Priority name 'Urgent' is not valid" }
+            }));
+        });
+
+        let client = test_client(&server);
+        let mut fields = Map::new();
+        fields.insert("priority".to_string(), json!({ "name": "Urgent" }));
+        let err = client.update_issue("ACME-1", fields).await.unwrap_err();
+
+        let jira = err.downcast_ref::<JiraError>().unwrap();
+        assert_eq!(jira.kind, ErrorKind::Validation);
+        assert_eq!(
+            jira.field_errors["priority"],
+            "Priority name 'Urgent' is not valid"
+        );
+        assert!(
+            err.to_string()
+                .contains("priority: Priority name 'Urgent' is not valid")
+        );
+    }
+
+    #[tokio::test]
+    async fn transition_issue_accepts_no_content_response() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/api/3/issue/ACME-1/transitions");
+            then.status(200).json_body(json!({
+                "transitions": [
+                    { "id": "11", "name": "To Do" },
+                    { "id": "21", "name": "In Progress" }
+                ]
+            }));
+        });
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/issue/ACME-1/transitions")
+                .json_body(json!({ "transition": { "id": "21" } }));
+            then.status(204);
+        });
+
+        let client = test_client(&server);
+        let response = client
+            .transition_issue("ACME-1", "in progress")
+            .await
+            .unwrap();
+
+        mock.assert();
+        assert_eq!(response, json!({}));
+    }
+
+    #[tokio::test]
+    async fn attachments_go_through_the_executor() {
+        let server = MockServer::start();
+        let download = server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/api/3/attachment/content/77")
+                .header("accept", "*/*");
+            then.status(503);
+        });
+        let upload = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/issue/ACME-1/attachments")
+                .header("x-atlassian-token", "no-check")
+                .header_exists("content-type")
+                .body_contains("filename=\"trace.txt\"")
+                .body_contains("Content-Type: text/plain\r\n\r\nstack trace\r\n");
+            then.status(400)
+                .json_body(json!({ "errorMessages": ["File too large"] }));
+        });
+
+        let mut client = test_client(&server);
+        let err = client.download_attachment("77").await.unwrap_err();
+        download.assert_hits(3);
+        assert_eq!(
+            err.downcast_ref::<JiraError>().unwrap().kind,
+            ErrorKind::Server
+        );
+        let err = client
+            .add_attachment("ACME-1", "trace.txt", "text/plain", b"stack trace".to_vec())
+            .await
+            .unwrap_err();
+        upload.assert_hits(1);
+        assert!(err.to_string().contains("File too large"));
+
+        client.dry_run = true;
+        assert!(client.download_attachment("77").await.unwrap().is_empty());
+        client
+            .add_attachment("ACME-1", "trace.txt", "text/plain", Vec::new())
+            .await
+            .unwrap();
+        download.assert_hits(3);
+        upload.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn execute_handles_each_response_shape() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/api/3/json")
+                .query_param("expand", "names");
+            then.status(200).json_body(json!({ "ok": true }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/blank");
+            then.status(200).body("  \n");
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/html");
+            then.status(200).body("<html>maintenance</html>");
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/json-error");
+            then.status(403)
+                .json_body(json!({ "errorMessages": ["You do not have permission"] }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/html-error");
+            then.status(400).body("<html>Bad request</html>");
+        });
+
+        let client = test_client(&server);
+
+        let value = client
+            .execute(Request::get("json").query("expand", "names"))
+            .await
+            .unwrap();
+        assert_eq!(value["ok"], true);
+
+        let value = client.execute(Request::get("blank")).await.unwrap();
+        assert_eq!(value, json!({}));
+
+        let err = client
+            .execute(Request::get("html").action("probe"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Failed to parse probe response"));
+
+        let err = client
+            .execute(Request::get("json-error"))
+            .await
+            .unwrap_err();
+        let jira = err.downcast_ref::<JiraError>().unwrap();
+        assert_eq!(jira.kind, ErrorKind::Permission);
+        assert_eq!(jira.messages, vec!["You do not have permission"]);
+
+        let err = client
+            .execute(Request::get("html-error"))
+            .await
+            .unwrap_err();
+        let jira = err.downcast_ref::<JiraError>().unwrap();
+        assert_eq!(jira.kind, ErrorKind::Validation);
+        assert_eq!(jira.messages, vec!["<html>Bad request</html>"]);
+    }
+}
@@ -0,0 +1,524 @@
+//! Copying issues, optionally into another project, and moving them there.
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+use crate::client::{JiraClient, Request, writable};
+use crate::error::{ErrorKind, JiraError};
+use crate::journal::Change;
+use crate::link::LinkRelation;
+use crate::models::Issue;
+
+/// Fields a clone never copies: set explicitly, derived by Jira, or holding
+/// data (links, attachments, work) that belongs to the original.
+pub const CLONE_SKIPPED_FIELDS: [&str; 10] = [
+    "project",
+    "issuetype",
+    "summary",
+    "parent",
+    "attachment",
+    "issuelinks",
+    "subtasks",
+    "timetracking",
+    "worklog",
+    "comment",
+];
+
+/// Sprint fields hold sprint objects but only accept a sprint id, so clones
+/// start in the backlog instead.
+const SPRINT_FIELD_TYPE: &str = "com.pyxis.greenhopper.jira:gh-sprint";
+
+/// Delay between checks on a move task.
+const MOVE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Checks before giving up on a move task that is still running.
+const MOVE_POLL_ATTEMPTS: u32 = 300;
+
+#[derive(Debug, Clone)]
+pub struct CloneOptions {
+    /// Target project; default: the original's.
+    pub project: Option<String>,
+    pub summary_prefix: String,
+    pub with_subtasks: bool,
+    pub with_links: bool,
+}
+
+impl Default for CloneOptions {
+    fn default() -> Self {
+        Self {
+            project: None,
+            summary_prefix: "CLONE - ".to_string(),
+            with_subtasks: false,
+            with_links: false,
+        }
+    }
+}
+
+/// A created clone with its cloned sub-tasks and copied links.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloneReport {
+    pub source: String,
+    pub key: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub subtasks: Vec<CloneReport>,
+    /// Copied links as `<verb> <KEY>`, e.g. `blocks ACME-9`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<String>,
+}
+
+impl JiraClient {
+    /// Creates a copy of `key` carrying every field the target project accepts,
+    /// custom fields included, and links it to the original with `Cloners`.
+    pub async fn clone_issue(&self, key: &str, options: &CloneOptions) -> Result<CloneReport> {
+        let source = self.get_issue(key).await?;
+        let source_project = source
+            .fields
+            .project
+            .as_ref()
+            .map(|p| p.key.clone())
+            .unwrap_or_default();
+        let project = options.project.clone().unwrap_or(source_project.clone());
+        // An epic or sub-task parent only carries over within the same project.
+        let parent = source
+            .fields
+            .parent
+            .as_ref()
+            .filter(|_| project == source_project)
+            .map(|parent| parent.key.clone());
+        let mut report = self
+            .clone_one(&source, &project, parent.as_deref(), options)
+            .await?;
+
+        if options.with_subtasks {
+            for subtask in source.fields.subtasks.iter().flatten() {
+                let subtask = self.get_issue(&subtask.key).await?;
+                let cloned = self
+                    .clone_one(&subtask, &project, Some(&report.key), options)
+                    .await?;
+                report.subtasks.push(cloned);
+            }
+        }
+        if options.with_links {
+            for link in source.fields.issuelinks.iter().flatten() {
+                let Some((verb, other)) = link.target() else {
+                    continue;
+                };
+                // Seen from the original, the other issue sits on the side it was posted on.
+                let other_is_outward = link.outward_issue.is_some();
+                match LinkRelation::from_link(&link.link_type.name, other_is_outward) {
+                    Some(relation) => {
+                        self.link_issues(&report.key, &other.key, relation).await?;
+                    }
+                    None => {
+                        self.link_with_type(
+                            &report.key,
+                            &other.key,
+                            &link.link_type.name,
+                            other_is_outward,
+                        )
+                        .await?;
+                    }
+                }
+                report.links.push(format!("{verb} {}", other.key));
+            }
+        }
+        Ok(report)
+    }
+
+    /// Links `key` to `to` with a link type [`LinkRelation`] does not cover.
+    /// Journaled links are [`LinkRelation`]s, so this one is recorded as
+    /// irreversible.
+    async fn link_with_type(
+        &self,
+        key: &str,
+        to: &str,
+        link_type: &str,
+        to_is_outward: bool,
+    ) -> Result<()> {
+        let (outward, inward) = if to_is_outward { (to, key) } else { (key, to) };
+        let body = json!({
+            "type": { "name": link_type },
+            "outwardIssue": { "key": outward },
+            "inwardIssue": { "key": inward }
+        });
+        self.execute(Request::post("issueLink", body).action("issue link"))
+            .await?;
+        self.record(Some(Change::Irreversible {
+            key: key.to_string(),
+            action: format!("'{link_type}' link to {to}"),
+        }))
+    }
+
+    /// Creates one copy of `source` and links it to the original.
+    async fn clone_one(
+        &self,
+        source: &Issue,
+        project: &str,
+        parent: Option<&str>,
+        options: &CloneOptions,
+    ) -> Result<CloneReport> {
+        let type_name = source
+            .fields
+            .issuetype
+            .as_ref()
+            .map(|t| t.name.as_str())
+            .unwrap_or("Task");
+        let issue_type = self.issue_type(project, type_name).await?;
+        let creatable: BTreeSet<String> = self
+            .create_fields(project, &issue_type.id)
+            .await?
+            .into_iter()
+            .filter(|field| field.custom.as_deref() != Some(SPRINT_FIELD_TYPE))
+            .map(|field| field.id)
+            .filter(|id| !CLONE_SKIPPED_FIELDS.contains(&id.as_str()))
+            .collect();
+
+        let mut fields: Map<String, Value> = match serde_json::to_value(&source.fields)? {
+            Value::Object(all) => all
+                .iter()
+                .filter(|(id, value)| creatable.contains(*id) && !value.is_null())
+                .map(|(id, value)| (id.clone(), writable(value)))
+                .collect(),
+            _ => Map::new(),
+        };
+        fields.insert("project".to_string(), json!({ "key": project }));
+        fields.insert("issuetype".to_string(), json!({ "id": issue_type.id }));
+        let summary = source.fields.summary.as_deref().unwrap_or_default();
+        fields.insert(
+            "summary".to_string(),
+            json!(format!("{}{summary}", options.summary_prefix)),
+        );
+        if let Some(parent) = parent {
+            fields.insert("parent".to_string(), json!({ "key": parent }));
+        }
+
+        let created: Issue = self
+            .execute_as(Request::post("issue", json!({ "fields": fields })).action("clone issue"))
+            .await?;
+        // A dry run creates nothing; stand in for the key so the follow-up requests still print.
+        let key = if self.dry_run {
+            format!("(clone of {})", source.key)
+        } else {
+            created.key
+        };
+        self.link_issues(&key, &source.key, LinkRelation::Clones)
+            .await?;
+        Ok(CloneReport {
+            source: source.key.clone(),
+            key,
+            subtasks: Vec::new(),
+            links: Vec::new(),
+        })
+    }
+
+    /// Moves `key` to another project and issue type, letting Jira pick
+    /// defaults for status, sub-task types and fields it cannot map, and waits
+    /// for the move to finish. Returns the issue under its new key, or `None`
+    /// under `dry_run`.
+    pub async fn move_issue(
+        &self,
+        key: &str,
+        project: &str,
+        issue_type: &str,
+    ) -> Result<Option<Issue>> {
+        let issue_type = self.issue_type(project, issue_type).await?;
+        self.bulk_move(key, &format!("{project},{}", issue_type.id))
+            .await
+    }
+
+    /// Runs Jira's bulk move for one issue and waits for it. `target` is
+    /// `<project>,<issue type id>`, plus `,<parent>` for a sub-task type.
+    pub(crate) async fn bulk_move(&self, key: &str, target: &str) -> Result<Option<Issue>> {
+        let body = json!({
+            "sendBulkNotification": false,
+            "targetToSourcesMapping": {
+                target: {
+                    "inferClassificationDefaults": true,
+                    "inferFieldDefaults": true,
+                    "inferStatusDefaults": true,
+                    "inferSubtaskTypeDefault": true,
+                    "issueIdsOrKeys": [key]
+                }
+            }
+        });
+        let task = self
+            .execute(Request::post("bulk/issues/move", body).action("move issue"))
+            .await?;
+        if self.dry_run {
+            return Ok(None);
+        }
+        let task_id = task["taskId"]
+            .as_str()
+            .map(str::to_string)
+            .or_else(|| task["taskId"].as_u64().map(|id| id.to_string()))
+            .ok_or_else(|| anyhow::anyhow!("Jira did not return a move task id"))?;
+
+        for _ in 0..MOVE_POLL_ATTEMPTS {
+            let progress = self
+                .execute(Request::get(format!("bulk/queue/{task_id}")).action("move progress"))
+                .await?;
+            match progress["status"].as_str().unwrap_or_default() {
+                "COMPLETE" => {
+                    let failures = move_failures(&progress);
+                    if !failures.is_empty() {
+                        return Err(JiraError::new(
+                            ErrorKind::Validation,
+                            format!("Failed to move {key}: {}", failures.join("; ")),
+                        )
+                        .into());
+                    }
+                    self.record(Some(Change::Irreversible {
+                        key: key.to_string(),
+                        action: move_action(target),
+                    }))?;
+                    return Ok(Some(self.get_issue(key).await?));
+                }
+                "FAILED" | "CANCELLED" | "DEAD" => {
+                    let failures = move_failures(&progress);
+                    let status = progress["status"].as_str().unwrap_or_default();
+                    return Err(JiraError::new(
+                        ErrorKind::Validation,
+                        format!("Move of {key} ended as {status}: {}", failures.join("; ")),
+                    )
+                    .into());
+                }
+                _ => tokio::time::sleep(MOVE_POLL_INTERVAL).await,
+            }
+        }
+        Err(anyhow::anyhow!(
+            "Move of {key} is still running; check task {task_id} later"
+        ))
+    }
+}
+
+/// How a [`JiraClient::bulk_move`] `target` reads in `history`, e.g. `move to ACME under ACME-1`.
+fn move_action(target: &str) -> String {
+    let mut parts = target.split(',');
+    let project = parts.next().unwrap_or_default();
+    match parts.nth(1) {
+        Some(parent) => format!("move to {project} under {parent}"),
+        None => format!("move to {project}"),
+    }
+}
+
+/// Error messages of a bulk move task, one per failed issue.
+fn move_failures(progress: &Value) -> Vec<String> {
+    let mut failures: Vec<String> = progress["failedAccessibleIssues"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .flat_map(|(_, errors)| {
+            errors
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+        })
+        .collect();
+    if progress["invalidOrInaccessibleIssueCount"]
+        .as_u64()
+        .unwrap_or(0)
+        > 0
+    {
+        failures.push("the issue is invalid or not accessible".to_string());
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_client;
+    use crate::journal::Journal;
+    use httpmock::prelude::*;
+
+    #[tokio::test]
+    async fn clone_copies_creatable_fields_and_links_to_the_original() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-1");
+            then.status(200).json_body(json!({
+                "key": "ACME-1",
+                "fields": {
+                    "summary": "Login fails",
+                    "project": { "id": "100", "key": "ACME" },
+                    "issuetype": { "id": "10001", "name": "Bug" },
+                    "status": { "name": "In Progress" },
+                    "priority": { "id": "2", "name": "High" },
+                    "labels": ["auth"],
+                    "customfield_10016": 5,
+                    "customfield_10020": [{ "id": 42, "name": "Sprint 7" }],
+                    "customfield_10099": null,
+                    "issuelinks": [{
+                        "id": "900",
+                        "type": { "name": "Blocks", "inward": "is blocked by", "outward": "blocks" },
+                        "outwardIssue": { "key": "ACME-9" }
+                    }, {
+                        "id": "901",
+                        "type": { "name": "Causes", "inward": "is caused by", "outward": "causes" },
+                        "inwardIssue": { "key": "ACME-8" }
+                    }]
+                }
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/api/3/issue/createmeta/BETA/issuetypes");
+            then.status(200).json_body(json!({
+                "issueTypes": [{ "id": "20001", "name": "bug" }],
+                "total": 1
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/api/3/issue/createmeta/BETA/issuetypes/20001");
+            then.status(200).json_body(json!({
+                "fields": [
+                    { "fieldId": "summary", "required": true },
+                    { "fieldId": "priority" },
+                    { "fieldId": "labels" },
+                    { "fieldId": "customfield_10016" },
+                    { "fieldId": "customfield_10099" },
+                    { "fieldId": "customfield_10020",
+                      "schema": { "custom": "com.pyxis.greenhopper.jira:gh-sprint" } }
+                ],
+                "total": 6
+            }));
+        });
+        let create = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/issue")
+                .json_body(json!({
+                    "fields": {
+                        "project": { "key": "BETA" },
+                        "issuetype": { "id": "20001" },
+                        "summary": "CLONE - Login fails",
+                        "priority": { "id": "2" },
+                        "labels": ["auth"],
+                        "customfield_10016": 5
+                    }
+                }));
+            then.status(201)
+                .json_body(json!({ "id": "5", "key": "BETA-5" }));
+        });
+        let cloners = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/issueLink")
+                .json_body(json!({
+                    "type": { "name": "Cloners" },
+                    "outwardIssue": { "key": "ACME-1" },
+                    "inwardIssue": { "key": "BETA-5" }
+                }));
+            then.status(201);
+        });
+        let blocks = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/issueLink")
+                .json_body(json!({
+                    "type": { "name": "Blocks" },
+                    "outwardIssue": { "key": "ACME-9" },
+                    "inwardIssue": { "key": "BETA-5" }
+                }));
+            then.status(201);
+        });
+        let causes = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/issueLink")
+                .json_body(json!({
+                    "type": { "name": "Causes" },
+                    "outwardIssue": { "key": "BETA-5" },
+                    "inwardIssue": { "key": "ACME-8" }
+                }));
+            then.status(201);
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/api/3/issue/BETA-5")
+                .query_param("fields", "issuelinks");
+            then.status(200)
+                .json_body(json!({ "key": "BETA-5", "fields": { "issuelinks": [] } }));
+        });
+        let journal = std::env::temp_dir().join(format!("jico-clone-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&journal);
+        let mut client = test_client(&server);
+        client.journal = Some(Journal::new(&journal, "jico clone"));
+
+        let options = CloneOptions {
+            project: Some("BETA".to_string()),
+            with_links: true,
+            ..CloneOptions::default()
+        };
+        let report = client.clone_issue("ACME-1", &options).await.unwrap();
+        let entries = client.journal.as_ref().unwrap().entries().unwrap();
+        std::fs::remove_file(&journal).unwrap();
+
+        create.assert();
+        cloners.assert();
+        blocks.assert();
+        causes.assert();
+        assert_eq!(report.key, "BETA-5");
+        assert_eq!(report.links, ["blocks ACME-9", "is caused by ACME-8"]);
+        let changes: Vec<Change> = entries.into_iter().map(|entry| entry.change).collect();
+        assert_eq!(
+            changes[1],
+            Change::Link {
+                key: "BETA-5".to_string(),
+                to: "ACME-9".to_string(),
+                relation: LinkRelation::Blocks,
+            }
+        );
+        assert_eq!(
+            changes[2],
+            Change::Irreversible {
+                key: "BETA-5".to_string(),
+                action: "'Causes' link to ACME-8".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn move_issue_waits_for_the_task_and_reports_failures() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/api/3/issue/createmeta/BETA/issuetypes");
+            then.status(200).json_body(json!({
+                "issueTypes": [{ "id": "20002", "name": "Story" }],
+                "total": 1
+            }));
+        });
+        let start = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/bulk/issues/move")
+                .json_body_partial(
+                    r#"{ "targetToSourcesMapping": { "BETA,20002": { "issueIdsOrKeys": ["ACME-1"] } } }"#,
+                );
+            then.status(201).json_body(json!({ "taskId": "7" }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/bulk/queue/7");
+            then.status(200).json_body(json!({
+                "status": "COMPLETE",
+                "failedAccessibleIssues": { "10001": ["Field 'Team' is required"] },
+                "invalidOrInaccessibleIssueCount": 0
+            }));
+        });
+
+        let err = test_client(&server)
+            .move_issue("ACME-1", "BETA", "story")
+            .await
+            .unwrap_err();
+
+        start.assert();
+        assert_eq!(
+            err.to_string(),
+            "Failed to move ACME-1: Field 'Team' is required"
+        );
+    }
+}
@@ -0,0 +1,318 @@
+//! Cycle time, lead time and time in status, reconstructed from changelogs.
+
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::Serialize;
+
+use crate::changelog::{ChangeSet, parse_jira_time, status_periods};
+use crate::client::JiraClient;
+use crate::models::Issue;
+
+/// Fields the report needs from each issue.
+const CYCLE_TIME_FIELDS: [&str; 4] = ["summary", "issuetype", "status", "created"];
+
+/// Which statuses start and finish the work. Names match case-insensitively.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleTimeOptions {
+    /// Entering any of these for the first time starts the cycle.
+    pub start: Vec<String>,
+    /// Entering any of these for the last time ends it, if the issue is still there.
+    pub done: Vec<String>,
+}
+
+impl Default for CycleTimeOptions {
+    fn default() -> Self {
+        Self {
+            start: vec!["In Progress".to_string()],
+            done: vec!["Done".to_string()],
+        }
+    }
+}
+
+/// Days an issue spent in one status, summed over every visit.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatusTime {
+    pub status: String,
+    pub days: f64,
+}
+
+/// One issue's row of the report. Times are in days.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueCycleTime {
+    pub key: String,
+    pub summary: String,
+    pub issue_type: String,
+    pub status: String,
+    pub created: String,
+    /// When the issue first entered a start status.
+    pub started: Option<String>,
+    /// When the issue last entered a done status.
+    pub done: Option<String>,
+    pub cycle_time_days: Option<f64>,
+    /// From creation to done.
+    pub lead_time_days: Option<f64>,
+    /// In order of first visit. The done status itself is left out once reached.
+    pub time_in_status: Vec<StatusTime>,
+}
+
+/// p50/p85/p95 by nearest rank, in days.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Percentiles {
+    pub p50: f64,
+    pub p85: f64,
+    pub p95: f64,
+}
+
+/// Percentiles over the finished issues of one type, or of all (`issue_type` `None`).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CycleTimeSummary {
+    pub issue_type: Option<String>,
+    /// Issues with a cycle time.
+    pub count: usize,
+    pub cycle_time: Option<Percentiles>,
+    pub lead_time: Option<Percentiles>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CycleTimeReport {
+    pub issues: Vec<IssueCycleTime>,
+    /// All issues first, then one entry per issue type.
+    pub summary: Vec<CycleTimeSummary>,
+}
+
+impl JiraClient {
+    /// Cycle and lead time of every issue matching `jql`, from its changelog.
+    pub async fn cycle_time_report(
+        &self,
+        jql: &str,
+        options: &CycleTimeOptions,
+    ) -> Result<CycleTimeReport> {
+        let issues = self.search_all(jql, &CYCLE_TIME_FIELDS).await?;
+        let keys: Vec<String> = issues.iter().map(|issue| issue.key.clone()).collect();
+        let changelogs = self.changelogs(&keys).await?;
+        let now = Utc::now().fixed_offset();
+        let rows = issues
+            .iter()
+            .zip(&changelogs)
+            .map(|(issue, changes)| issue_cycle_time(issue, changes, options, now))
+            .collect();
+        Ok(cycle_time_summary(rows))
+    }
+}
+
+fn is_one_of(status: &str, names: &[String]) -> bool {
+    names.iter().any(|name| name.eq_ignore_ascii_case(status))
+}
+
+fn days_between(from: DateTime<FixedOffset>, to: DateTime<FixedOffset>) -> f64 {
+    (to - from).num_seconds() as f64 / 86_400.0
+}
+
+/// Rounds to two decimals for display.
+fn round_days(days: f64) -> f64 {
+    (days * 100.0).round() / 100.0
+}
+
+/// Works out one issue's row from its changelog; periods still open end at `now`.
+pub fn issue_cycle_time(
+    issue: &Issue,
+    changes: &[ChangeSet],
+    options: &CycleTimeOptions,
+    now: DateTime<FixedOffset>,
+) -> IssueCycleTime {
+    let fields = &issue.fields;
+    let status = issue.status_name().unwrap_or_default();
+    let created_text = fields.created.clone().unwrap_or_default();
+    let created = parse_jira_time(&created_text).unwrap_or(now);
+    let periods = status_periods(created, status, changes);
+    let finished = is_one_of(status, &options.done);
+
+    let started = periods
+        .iter()
+        .find(|p| is_one_of(&p.status, &options.start))
+        .map(|p| p.since);
+    let done = periods
+        .iter()
+        .rev()
+        .find(|p| finished && is_one_of(&p.status, &options.done))
+        .map(|p| p.since);
+
+    let mut time_in_status: Vec<StatusTime> = Vec::new();
+    for period in &periods {
+        if finished && period.until.is_none() {
+            continue;
+        }
+        let days = days_between(period.since, period.until.unwrap_or(now));
+        match time_in_status
+            .iter_mut()
+            .find(|t| t.status == period.status)
+        {
+            Some(total) => total.days += days,
+            None => time_in_status.push(StatusTime {
+                status: period.status.clone(),
+                days,
+            }),
+        }
+    }
+    for total in &mut time_in_status {
+        total.days = round_days(total.days);
+    }
+
+    let cycle_time = match (started, done) {
+        (Some(start), Some(end)) if start <= end => Some(round_days(days_between(start, end))),
+        _ => None,
+    };
+    IssueCycleTime {
+        key: issue.key.clone(),
+        summary: fields.summary.clone().unwrap_or_default(),
+        issue_type: fields
+            .issuetype
+            .as_ref()
+            .map(|t| t.name.clone())
+            .unwrap_or_default(),
+        status: status.to_string(),
+        created: created_text,
+        started: started.map(|at| at.to_rfc3339()),
+        done: done.map(|at| at.to_rfc3339()),
+        cycle_time_days: cycle_time,
+        lead_time_days: done.map(|end| round_days(days_between(created, end))),
+        time_in_status,
+    }
+}
+
+/// The nearest-rank percentiles of `values`, or `None` when there are none.
+pub fn percentiles(values: &[f64]) -> Option<Percentiles> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let rank = |p: f64| {
+        let index = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+        sorted[index.clamp(1, sorted.len()) - 1]
+    };
+    Some(Percentiles {
+        p50: rank(50.0),
+        p85: rank(85.0),
+        p95: rank(95.0),
+    })
+}
+
+/// Adds the overall and per-issue-type percentiles to `rows`.
+pub fn cycle_time_summary(rows: Vec<IssueCycleTime>) -> CycleTimeReport {
+    let mut types: Vec<&str> = Vec::new();
+    for row in &rows {
+        if !types.contains(&row.issue_type.as_str()) {
+            types.push(&row.issue_type);
+        }
+    }
+    let summarize = |issue_type: Option<&str>| {
+        let rows: Vec<&IssueCycleTime> = rows
+            .iter()
+            .filter(|row| issue_type.is_none_or(|t| row.issue_type == t))
+            .collect();
+        let cycle: Vec<f64> = rows.iter().filter_map(|r| r.cycle_time_days).collect();
+        let lead: Vec<f64> = rows.iter().filter_map(|r| r.lead_time_days).collect();
+        CycleTimeSummary {
+            issue_type: issue_type.map(str::to_string),
+            count: cycle.len(),
+            cycle_time: percentiles(&cycle),
+            lead_time: percentiles(&lead),
+        }
+    };
+    let mut summary = vec![summarize(None)];
+    summary.extend(types.iter().map(|t| summarize(Some(t))));
+    CycleTimeReport {
+        issues: rows,
+        summary,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_client;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    #[test]
+    fn percentiles_use_the_nearest_rank() {
+        let values: Vec<f64> = (1..=20).map(f64::from).collect();
+        assert_eq!(
+            percentiles(&values),
+            Some(Percentiles {
+                p50: 10.0,
+                p85: 17.0,
+                p95: 19.0
+            })
+        );
+        assert_eq!(percentiles(&[]), None);
+    }
+
+    #[tokio::test]
+    async fn report_measures_from_first_start_to_last_done() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST).path("/rest/api/3/search/jql");
+            then.status(200).json_body(json!({
+                "issues": [{
+                    "key": "ACME-1",
+                    "fields": {
+                        "summary": "Login", "issuetype": { "name": "Story" },
+                        "status": { "name": "Done" },
+                        "created": "2026-10-01T00:00:00.000+0000"
+                    }
+                }],
+                "isLast": true
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-1/changelog");
+            then.status(200).json_body(json!({
+                "total": 4, "isLast": true,
+                "values": [
+                    { "id": "1", "created": "2026-10-02T00:00:00.000+0000",
+                      "items": [{ "field": "status", "fromString": "To Do", "toString": "In Progress" }] },
+                    { "id": "2", "created": "2026-10-03T12:00:00.000+0000",
+                      "items": [{ "field": "status", "fromString": "In Progress", "toString": "Done" }] },
+                    { "id": "3", "created": "2026-10-04T00:00:00.000+0000",
+                      "items": [{ "field": "status", "fromString": "Done", "toString": "In Progress" }] },
+                    { "id": "4", "created": "2026-10-05T00:00:00.000+0000",
+                      "items": [{ "field": "status", "fromString": "In Progress", "toString": "Done" }] }
+                ]
+            }));
+        });
+        let client = test_client(&server);
+
+        let report = client
+            .cycle_time_report("project = ACME", &CycleTimeOptions::default())
+            .await
+            .unwrap();
+
+        let row = &report.issues[0];
+        assert_eq!(row.cycle_time_days, Some(3.0));
+        assert_eq!(row.lead_time_days, Some(4.0));
+        assert_eq!(
+            row.time_in_status,
+            [
+                StatusTime {
+                    status: "To Do".to_string(),
+                    days: 1.0
+                },
+                StatusTime {
+                    status: "In Progress".to_string(),
+                    days: 2.5
+                },
+                StatusTime {
+                    status: "Done".to_string(),
+                    days: 0.5
+                }
+            ]
+        );
+        assert_eq!(report.summary.len(), 2);
+        assert_eq!(report.summary[1].issue_type.as_deref(), Some("Story"));
+        assert_eq!(report.summary[0].cycle_time.unwrap().p95, 3.0);
+    }
+}
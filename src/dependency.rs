@@ -0,0 +1,261 @@
+//! What blocks an issue, and the longest chain of blockers in an epic.
+//!
+//! Both analyses walk `Blocks` links backwards from blocked to blocker,
+//! stopping at resolved issues: a done blocker no longer holds anything up.
+
+use std::collections::{BTreeMap, HashSet, VecDeque};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::client::{JiraClient, Request};
+use crate::graph::{GRAPH_FIELDS, GraphNode, LinkGraph};
+use crate::link::LinkRelation;
+use crate::models::Issue;
+
+/// An unresolved issue that directly or transitively blocks another.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Blocker {
+    #[serde(flatten)]
+    pub issue: GraphNode,
+    /// Links between this issue and the blocked one; 1 for direct blockers.
+    pub distance: usize,
+    /// Issues in the result (or the blocked issue) that this one blocks directly.
+    pub blocks: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CriticalPath {
+    pub epic: String,
+    /// From the first issue to finish to the last, each blocking the next.
+    pub path: Vec<GraphNode>,
+    /// Blocking loops found on the way; their links are left out of the path.
+    pub cycles: Vec<Vec<String>>,
+}
+
+impl JiraClient {
+    /// Every unresolved issue blocking `key`, nearest first.
+    pub async fn blockers(&self, key: &str) -> Result<Vec<Blocker>> {
+        let root: Issue = self
+            .execute_as(
+                Request::get(format!("issue/{key}"))
+                    .query("fields", GRAPH_FIELDS.join(","))
+                    .action("get issue"),
+            )
+            .await?;
+        let root_key = root.key.clone();
+        let issues = self.with_blockers(vec![root]).await?;
+        let graph = blocking_graph(&issues);
+        Ok(blockers_of(&graph, &root_key))
+    }
+
+    /// The longest chain of unresolved blockers among an epic's open issues
+    /// and whatever blocks them, inside or outside the epic.
+    pub async fn critical_path(&self, epic: &str) -> Result<CriticalPath> {
+        let children = self.epic_children(epic, &["issuelinks"]).await?;
+        let open = children
+            .into_iter()
+            .filter(|issue| !issue.is_done())
+            .collect();
+        let issues = self.with_blockers(open).await?;
+        let graph = blocking_graph(&issues);
+        Ok(CriticalPath {
+            epic: epic.to_string(),
+            path: longest_chain(&graph),
+            cycles: graph.cycles(LinkRelation::Blocks.link_type_name()),
+        })
+    }
+
+    /// `issues` plus all of their unresolved blockers, fetched level by level.
+    async fn with_blockers(&self, issues: Vec<Issue>) -> Result<Vec<Issue>> {
+        let mut seen: HashSet<String> = issues.iter().map(|i| i.key.clone()).collect();
+        let mut all = issues;
+        let mut level = 0..all.len();
+        loop {
+            let next: Vec<String> = all[level]
+                .iter()
+                .flat_map(open_blockers)
+                .filter(|key| seen.insert(key.to_string()))
+                .map(str::to_string)
+                .collect();
+            if next.is_empty() {
+                return Ok(all);
+            }
+            let start = all.len();
+            all.extend(self.search_keys("key", &next, &GRAPH_FIELDS).await?);
+            level = start..all.len();
+        }
+    }
+}
+
+/// Keys of the unresolved issues that block `issue` directly.
+pub(crate) fn open_blockers(issue: &Issue) -> impl Iterator<Item = &str> {
+    issue
+        .fields
+        .issuelinks
+        .iter()
+        .flatten()
+        .filter_map(|link| {
+            let other = link.outward_issue.as_ref().or(link.inward_issue.as_ref())?;
+            let relation =
+                LinkRelation::from_link(&link.link_type.name, link.outward_issue.is_some());
+            (relation == Some(LinkRelation::BlockedBy)).then_some(other)
+        })
+        .filter(|other| !other.is_done())
+        .map(|other| other.key.as_str())
+}
+
+/// The `Blocks` links among `issues`.
+fn blocking_graph(issues: &[Issue]) -> LinkGraph {
+    LinkGraph::from_issues(issues, &[LinkRelation::Blocks.link_type_name().to_string()])
+}
+
+fn blockers_of(graph: &LinkGraph, key: &str) -> Vec<Blocker> {
+    let mut distance = BTreeMap::from([(key, 0)]);
+    let mut queue = VecDeque::from([key]);
+    let mut order = Vec::new();
+    while let Some(current) = queue.pop_front() {
+        for blocker in graph.related(current, LinkRelation::BlockedBy) {
+            let open = graph.nodes.get(blocker).is_some_and(|n| !n.is_done());
+            if open && !distance.contains_key(blocker) {
+                distance.insert(blocker, distance[current] + 1);
+                order.push(blocker);
+                queue.push_back(blocker);
+            }
+        }
+    }
+    order
+        .into_iter()
+        .map(|blocker| Blocker {
+            issue: graph.nodes[blocker].clone(),
+            distance: distance[blocker],
+            blocks: graph
+                .related(blocker, LinkRelation::Blocks)
+                .into_iter()
+                .filter(|blocked| distance.contains_key(blocked))
+                .map(str::to_string)
+                .collect(),
+        })
+        .collect()
+}
+
+/// Longest path through unresolved issues; links closing a loop are skipped.
+fn longest_chain(graph: &LinkGraph) -> Vec<GraphNode> {
+    fn longest<'a>(
+        graph: &'a LinkGraph,
+        key: &'a str,
+        visiting: &mut HashSet<&'a str>,
+        memo: &mut BTreeMap<&'a str, Vec<&'a str>>,
+    ) -> Vec<&'a str> {
+        if let Some(chain) = memo.get(key) {
+            return chain.clone();
+        }
+        visiting.insert(key);
+        let mut best: Vec<&str> = Vec::new();
+        for next in graph.related(key, LinkRelation::Blocks) {
+            let open = graph.nodes.get(next).is_some_and(|n| !n.is_done());
+            if !open || visiting.contains(next) {
+                continue;
+            }
+            let chain = longest(graph, next, visiting, memo);
+            if chain.len() > best.len() {
+                best = chain;
+            }
+        }
+        visiting.remove(key);
+        best.insert(0, key);
+        memo.insert(key, best.clone());
+        best
+    }
+
+    let mut memo = BTreeMap::new();
+    let mut best: Vec<&str> = Vec::new();
+    for (key, node) in &graph.nodes {
+        if node.is_done() {
+            continue;
+        }
+        let chain = longest(graph, key, &mut HashSet::new(), &mut memo);
+        if chain.len() > best.len() {
+            best = chain;
+        }
+    }
+    best.into_iter()
+        .map(|key| graph.nodes[key].clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    /// `key` with the given status category, blocked by each of `blocked_by`.
+    fn issue(key: &str, category: &str, blocked_by: &[(&str, &str)]) -> Issue {
+        let links: Vec<Value> = blocked_by
+            .iter()
+            .map(|(other, other_category)| {
+                json!({
+                    "type": { "name": "Blocks", "inward": "is blocked by", "outward": "blocks" },
+                    "inwardIssue": {
+                        "key": other,
+                        "fields": { "status": { "name": other_category, "statusCategory": { "key": other_category } } }
+                    }
+                })
+            })
+            .collect();
+        serde_json::from_value(json!({
+            "key": key,
+            "fields": {
+                "status": { "name": category, "statusCategory": { "key": category } },
+                "issuelinks": links
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn blockers_are_transitive_and_skip_resolved_issues() {
+        let issues = [
+            issue("ACME-1", "new", &[("ACME-2", "new"), ("ACME-3", "done")]),
+            issue("ACME-2", "indeterminate", &[("ACME-4", "new")]),
+            issue("ACME-4", "new", &[]),
+        ];
+        assert_eq!(open_blockers(&issues[0]).collect::<Vec<_>>(), ["ACME-2"]);
+
+        let graph = blocking_graph(&issues);
+        let blockers = blockers_of(&graph, "ACME-1");
+
+        let found: Vec<(&str, usize)> = blockers
+            .iter()
+            .map(|b| (b.issue.key.as_str(), b.distance))
+            .collect();
+        assert_eq!(found, [("ACME-2", 1), ("ACME-4", 2)]);
+        assert_eq!(blockers[0].blocks, ["ACME-1"]);
+        assert_eq!(blockers[1].blocks, ["ACME-2"]);
+    }
+
+    #[test]
+    fn longest_chain_ignores_resolved_issues_and_loops() {
+        // ACME-5 → ACME-4 → ACME-2 → ACME-1 is the longest open chain;
+        // ACME-3 is done and ACME-6 ↔ ACME-7 block each other.
+        let issues = [
+            issue("ACME-1", "new", &[("ACME-2", "new"), ("ACME-3", "done")]),
+            issue("ACME-2", "new", &[("ACME-4", "new")]),
+            issue("ACME-4", "new", &[("ACME-5", "indeterminate")]),
+            issue("ACME-5", "indeterminate", &[]),
+            issue("ACME-6", "new", &[("ACME-7", "new")]),
+            issue("ACME-7", "new", &[("ACME-6", "new")]),
+        ];
+        let graph = blocking_graph(&issues);
+
+        let path: Vec<String> = longest_chain(&graph).into_iter().map(|n| n.key).collect();
+
+        assert_eq!(path, ["ACME-5", "ACME-4", "ACME-2", "ACME-1"]);
+        assert_eq!(
+            graph.cycles(LinkRelation::Blocks.link_type_name()),
+            [["ACME-6", "ACME-7", "ACME-6"]]
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//! Epics: their child issues and progress.
+//!
+//! Children are found through the `parent` field, which both company-managed
+//! and team-managed projects use for the epic → issue relation.
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+use crate::client::{JiraClient, Request};
+use crate::error::{ErrorKind, JiraError};
+use crate::models::{Issue, SearchResults};
+
+/// Fields fetched for epics and their children.
+pub const EPIC_FIELDS: [&str; 4] = ["summary", "status", "assignee", "issuetype"];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpicProgress {
+    pub epic: String,
+    pub total: usize,
+    pub todo: usize,
+    pub in_progress: usize,
+    pub done: usize,
+    pub percent_done: f64,
+    pub story_points: f64,
+    pub story_points_done: f64,
+    /// Children without a story points value.
+    pub unestimated: usize,
+}
+
+impl EpicProgress {
+    /// Tallies children by status category and sums their story points.
+    pub fn from_children(epic: &str, children: &[Issue], point_fields: &[String]) -> Self {
+        let mut progress = EpicProgress {
+            epic: epic.to_string(),
+            total: children.len(),
+            ..Self::default()
+        };
+        for child in children {
+            match child.status_category() {
+                Some("done") => progress.done += 1,
+                Some("indeterminate") => progress.in_progress += 1,
+                _ => progress.todo += 1,
+            }
+            match story_points(child, point_fields) {
+                Some(points) => {
+                    progress.story_points += points;
+                    if child.is_done() {
+                        progress.story_points_done += points;
+                    }
+                }
+                None => progress.unestimated += 1,
+            }
+        }
+        if progress.total > 0 {
+            let percent = progress.done as f64 * 100.0 / progress.total as f64;
+            progress.percent_done = (percent * 10.0).round() / 10.0;
+        }
+        progress
+    }
+}
+
+/// The first numeric value among `point_fields` on the issue.
+pub fn story_points(issue: &Issue, point_fields: &[String]) -> Option<f64> {
+    point_fields
+        .iter()
+        .find_map(|field| issue.fields.extra.get(field).and_then(Value::as_f64))
+}
+
+impl JiraClient {
+    pub async fn list_epics(&self, project: &str, limit: u32) -> Result<SearchResults> {
+        let jql = format!("project = {project} AND issuetype = Epic ORDER BY created DESC");
+        self.search(&jql, &EPIC_FIELDS, limit, None).await
+    }
+
+    pub async fn epic_children(&self, epic: &str, extra_fields: &[&str]) -> Result<Vec<Issue>> {
+        let mut fields = EPIC_FIELDS.to_vec();
+        fields.extend_from_slice(extra_fields);
+        self.search_all(&format!("parent = {epic}"), &fields).await
+    }
+
+    /// Sets (or with `None`, clears) an issue's parent.
+    pub async fn set_parent(&self, key: &str, parent: Option<&str>) -> Result<Value> {
+        let mut fields = Map::new();
+        let value = parent.map(|parent| json!({ "key": parent }));
+        fields.insert("parent".to_string(), value.unwrap_or(Value::Null));
+        self.update_issue(key, fields).await
+    }
+
+    /// Clears the parent of `key`, refusing when it is not a child of `epic`.
+    pub async fn remove_from_epic(&self, epic: &str, key: &str) -> Result<Value> {
+        let issue: Issue = self
+            .execute_as(
+                Request::get(format!("issue/{key}"))
+                    .query("fields", "parent")
+                    .action("get issue"),
+            )
+            .await?;
+        let parent = issue.fields.parent.as_ref().map(|p| p.key.as_str());
+        if parent != Some(epic) {
+            return Err(JiraError::new(
+                ErrorKind::Validation,
+                format!("{key} is not in epic {epic}"),
+            )
+            .into());
+        }
+        self.set_parent(key, None).await
+    }
+
+    pub async fn epic_progress(&self, epic: &str) -> Result<EpicProgress> {
+        let point_fields = self.story_point_fields().await?;
+        let extra: Vec<&str> = point_fields.iter().map(String::as_str).collect();
+        let children = self.epic_children(epic, &extra).await?;
+        Ok(EpicProgress::from_children(epic, &children, &point_fields))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn child(key: &str, category: &str, points: Value) -> Issue {
+        serde_json::from_value(json!({
+            "key": key,
+            "fields": {
+                "status": { "name": category, "statusCategory": { "key": category } },
+                "customfield_10016": points
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn epic_progress_counts_categories_and_points() {
+        let children = [
+            child("ACME-2", "done", json!(3)),
+            child("ACME-3", "indeterminate", json!(5)),
+            child("ACME-4", "new", json!(null)),
+            child("ACME-5", "done", json!(2.5)),
+        ];
+        let fields = vec![
+            "customfield_10028".to_string(),
+            "customfield_10016".to_string(),
+        ];
+
+        let progress = EpicProgress::from_children("ACME-1", &children, &fields);
+
+        assert_eq!(progress.total, 4);
+        assert_eq!(
+            (progress.todo, progress.in_progress, progress.done),
+            (1, 1, 2)
+        );
+        assert_eq!(progress.story_points, 10.5);
+        assert_eq!(progress.story_points_done, 5.5);
+        assert_eq!(progress.unestimated, 1);
+        assert_eq!(progress.percent_done, 50.0);
+    }
+}
@@ -0,0 +1,202 @@
+use reqwest::StatusCode;
+use serde_json::{Map, Value, json};
+
+/// Broad failure categories; each maps to its own process exit code so scripts can branch on them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Auth,
+    Permission,
+    NotFound,
+    Validation,
+    RateLimit,
+    Server,
+    Network,
+    Config,
+    Other,
+}
+
+impl ErrorKind {
+    pub fn from_status(status: StatusCode) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED => ErrorKind::Auth,
+            StatusCode::FORBIDDEN => ErrorKind::Permission,
+            StatusCode::NOT_FOUND => ErrorKind::NotFound,
+            StatusCode::TOO_MANY_REQUESTS => ErrorKind::RateLimit,
+            status if status.is_client_error() => ErrorKind::Validation,
+            status if status.is_server_error() => ErrorKind::Server,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorKind::Auth => "auth",
+            ErrorKind::Permission => "permission",
+            ErrorKind::NotFound => "not_found",
+            ErrorKind::Validation => "validation",
+            ErrorKind::RateLimit => "rate_limit",
+            ErrorKind::Server => "server",
+            ErrorKind::Network => "network",
+            ErrorKind::Config => "config",
+            ErrorKind::Other => "other",
+        }
+    }
+
+    /// Classifies any error by the first `JiraError` or transport failure in its chain.
+    pub fn of(err: &anyhow::Error) -> Self {
+        for cause in err.chain() {
+            if let Some(jira) = cause.downcast_ref::<JiraError>() {
+                return jira.kind;
+            }
+            if cause.downcast_ref::<reqwest::Error>().is_some() {
+                return ErrorKind::Network;
+            }
+        }
+        ErrorKind::Other
+    }
+
+    pub fn exit_code(self) -> u8 {
+        // 2 is left to clap for usage errors.
+        match self {
+            ErrorKind::Other => 1,
+            ErrorKind::Config => 3,
+            ErrorKind::Auth => 4,
+            ErrorKind::Permission => 5,
+            ErrorKind::NotFound => 6,
+            ErrorKind::Validation => 7,
+            ErrorKind::RateLimit => 8,
+            ErrorKind::Network => 9,
+            ErrorKind::Server => 10,
+        }
+    }
+}
+
+/// An error reported by Jira (or detected before talking to it), with Jira's
+/// `errorMessages` and per-field `errors` pulled out of the response body.
+#[derive(Debug)]
+pub struct JiraError {
+    pub kind: ErrorKind,
+    pub status: Option<StatusCode>,
+    pub messages: Vec<String>,
+    pub field_errors: Map<String, Value>,
+}
+
+impl JiraError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            status: None,
+            messages: vec![message.into()],
+            field_errors: Map::new(),
+        }
+    }
+
+    /// Builds an error from a raw response body, which may be JSON, HTML or empty.
+    pub fn from_body(status: StatusCode, body: &[u8]) -> Self {
+        if let Ok(value) = serde_json::from_slice::<Value>(body) {
+            return Self::from_response(status, &value);
+        }
+        let mut error = Self::from_response(status, &json!({}));
+        let text = String::from_utf8_lossy(body);
+        let text = text.trim();
+        if !text.is_empty() {
+            error.messages.push(text.chars().take(200).collect());
+        }
+        error
+    }
+
+    pub fn from_response(status: StatusCode, body: &Value) -> Self {
+        let messages = body
+            .get("errorMessages")
+            .and_then(|m| m.as_array())
+            .map(|m| {
+                m.iter()
+                    .filter_map(|m| m.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let field_errors = body
+            .get("errors")
+            .and_then(|e| e.as_object())
+            .cloned()
+            .unwrap_or_default();
+        Self {
+            kind: ErrorKind::from_status(status),
+            status: Some(status),
+            messages,
+            field_errors,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "error": {
+                "kind": self.kind.name(),
+                "status": self.status.map(|s| s.as_u16()),
+                "messages": self.messages,
+                "errors": self.field_errors,
+                "exitCode": self.kind.exit_code(),
+            }
+        })
+    }
+
+    /// Jira's messages followed by one `field: message` line per field error.
+    pub fn details(&self) -> Vec<String> {
+        let mut lines = self.messages.to_vec();
+        lines.extend(
+            self.field_errors
+                .iter()
+                .map(|(field, message)| match message {
+                    Value::String(message) => format!("{field}: {message}"),
+                    other => format!("{field}: {other}"),
+                }),
+        );
+        lines
+    }
+}
+
+impl std::fmt::Display for JiraError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines = self.details();
+        match self.status {
+            Some(status) => {
+                write!(f, "Jira returned error status {status}")?;
+                for line in &lines {
+                    write!(f, "\n  - {line}")?;
+                }
+                Ok(())
+            }
+            None => write!(f, "{}", lines.join("; ")),
+        }
+    }
+}
+
+impl std::error::Error for JiraError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn jira_error_maps_status_to_kind_and_exit_code() {
+        let cases = [
+            (StatusCode::UNAUTHORIZED, ErrorKind::Auth, 4),
+            (StatusCode::FORBIDDEN, ErrorKind::Permission, 5),
+            (StatusCode::NOT_FOUND, ErrorKind::NotFound, 6),
+            (StatusCode::BAD_REQUEST, ErrorKind::Validation, 7),
+            (StatusCode::TOO_MANY_REQUESTS, ErrorKind::RateLimit, 8),
+            (StatusCode::BAD_GATEWAY, ErrorKind::Server, 10),
+        ];
+        for (status, kind, code) in cases {
+            let err = JiraError::from_response(status, &json!({}));
+            assert_eq!(err.kind, kind);
+            assert_eq!(err.kind.exit_code(), code);
+        }
+
+        let err = anyhow::Error::from(JiraError::new(ErrorKind::Config, "Missing JIRA_EMAIL"))
+            .context("Failed to start");
+        assert_eq!(ErrorKind::of(&err), ErrorKind::Config);
+        assert_eq!(ErrorKind::of(&anyhow!("plain failure")), ErrorKind::Other);
+    }
+}
@@ -0,0 +1,196 @@
+//! Cumulative flow: how many issues sat in each status over time,
+//! reconstructed from changelogs.
+
+use anyhow::Result;
+use chrono::{DateTime, Days, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc};
+use serde::Serialize;
+
+use crate::changelog::{StatusPeriod, parse_jira_time, status_periods};
+use crate::client::JiraClient;
+use crate::error::{ErrorKind, JiraError};
+
+/// Fields needed to replay each issue's status.
+const FLOW_FIELDS: [&str; 2] = ["status", "created"];
+
+/// Spacing of the samples.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Interval {
+    /// Every day
+    #[default]
+    Day,
+    /// Every seventh day, starting with the first
+    Week,
+}
+
+impl Interval {
+    fn days(self) -> u64 {
+        match self {
+            Interval::Day => 1,
+            Interval::Week => 7,
+        }
+    }
+}
+
+/// Issue counts at the end of one day, in the order of [`CumulativeFlow::statuses`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FlowPoint {
+    /// `YYYY-MM-DD`.
+    pub date: String,
+    pub counts: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CumulativeFlow {
+    /// To do statuses first and done statuses last, otherwise in order of
+    /// first appearance.
+    pub statuses: Vec<String>,
+    pub points: Vec<FlowPoint>,
+}
+
+/// Rank of a status category in the flow: to do, in progress, done.
+fn category_rank(category: Option<&str>) -> u8 {
+    match category {
+        Some("new") => 0,
+        Some("done") => 2,
+        _ => 1,
+    }
+}
+
+impl JiraClient {
+    /// Counts the issues matching `jql` per status at the end of every
+    /// `interval` from `from` to `to` (inclusive; never past now), with days
+    /// in the timezone `tz`. Issues count from their creation on.
+    pub async fn cumulative_flow<Tz: TimeZone>(
+        &self,
+        jql: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        interval: Interval,
+        tz: &Tz,
+    ) -> Result<CumulativeFlow> {
+        if from > to {
+            return Err(JiraError::new(
+                ErrorKind::Validation,
+                format!("--from {from} is after --to {to}"),
+            )
+            .into());
+        }
+        let issues = self.search_all(jql, &FLOW_FIELDS).await?;
+        let keys: Vec<String> = issues.iter().map(|issue| issue.key.clone()).collect();
+        let changelogs = self.changelogs(&keys).await?;
+
+        let now = Utc::now().fixed_offset();
+        let mut statuses: Vec<(u8, String)> = Vec::new();
+        let mut histories: Vec<Vec<StatusPeriod>> = Vec::new();
+        for (issue, changes) in issues.iter().zip(&changelogs) {
+            let created = issue.fields.created.as_deref().and_then(parse_jira_time);
+            let Some(created) = created else {
+                continue;
+            };
+            let status = issue.status_name().unwrap_or_default();
+            let periods = status_periods(created, status, changes);
+            for period in &periods {
+                if !statuses.iter().any(|(_, s)| *s == period.status) {
+                    statuses.push((1, period.status.clone()));
+                }
+            }
+            histories.push(periods);
+        }
+        // Categories are only known for statuses some issue is in now.
+        for issue in &issues {
+            if let Some(entry) = statuses
+                .iter_mut()
+                .find(|(_, s)| Some(s.as_str()) == issue.status_name())
+            {
+                entry.0 = category_rank(issue.status_category());
+            }
+        }
+        statuses.sort_by_key(|(rank, _)| *rank);
+        let statuses: Vec<String> = statuses.into_iter().map(|(_, s)| s).collect();
+
+        let mut points = Vec::new();
+        let mut date = from;
+        while date <= to {
+            if start_of_day(date, tz) > now {
+                break;
+            }
+            let at = start_of_day(date + Days::new(1), tz).min(now);
+            let mut counts = vec![0; statuses.len()];
+            for periods in &histories {
+                let current = periods
+                    .iter()
+                    .find(|p| p.since <= at && p.until.is_none_or(|until| at < until));
+                if let Some(period) = current
+                    && let Some(i) = statuses.iter().position(|s| *s == period.status)
+                {
+                    counts[i] += 1;
+                }
+            }
+            points.push(FlowPoint {
+                date: date.format("%Y-%m-%d").to_string(),
+                counts,
+            });
+            date = date + Days::new(interval.days());
+        }
+        Ok(CumulativeFlow { statuses, points })
+    }
+}
+
+/// Midnight at the start of `date` in `tz`.
+fn start_of_day<Tz: TimeZone>(date: NaiveDate, tz: &Tz) -> DateTime<FixedOffset> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    tz.from_local_datetime(&midnight)
+        .earliest()
+        .map(|at| at.fixed_offset())
+        .unwrap_or_else(|| midnight.and_utc().fixed_offset())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_client;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn flow_counts_issues_per_status_at_each_day_end() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST).path("/rest/api/3/search/jql");
+            then.status(200)
+                .json_body(json!({ "isLast": true, "issues": [
+                { "key": "ACME-1", "fields": {
+                    "status": { "name": "Done", "statusCategory": { "key": "done" } },
+                    "created": "2026-10-01T08:00:00.000+0000" } },
+                { "key": "ACME-2", "fields": {
+                    "status": { "name": "To Do", "statusCategory": { "key": "new" } },
+                    "created": "2026-10-02T08:00:00.000+0000" } }
+            ] }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-1/changelog");
+            then.status(200).json_body(json!({ "isLast": true, "values": [
+                { "id": "1", "created": "2026-10-02T10:00:00.000+0000",
+                  "items": [{ "field": "status", "fromString": "In Progress", "toString": "Done" }] }
+            ] }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-2/changelog");
+            then.status(200)
+                .json_body(json!({ "isLast": true, "values": [] }));
+        });
+        let client = test_client(&server);
+        let day = |d: u32| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+
+        let flow = client
+            .cumulative_flow("project = ACME", day(1), day(3), Interval::Day, &Utc)
+            .await
+            .unwrap();
+
+        assert_eq!(flow.statuses, ["To Do", "In Progress", "Done"]);
+        let counts: Vec<&[usize]> = flow.points.iter().map(|p| p.counts.as_slice()).collect();
+        assert_eq!(counts, [&[0, 1, 0][..], &[1, 0, 1], &[1, 0, 1]]);
+        assert_eq!(flow.points[0].date, "2026-10-01");
+    }
+}
@@ -0,0 +1,492 @@
+//! Issue link graphs, rendered as Graphviz DOT or Mermaid.
+//!
+//! Edges always point in the link type's outward direction, the direction
+//! [`LinkRelation::outward_inward_keys`](crate::LinkRelation::outward_inward_keys)
+//! encodes when creating links: for `Blocks` that is blocker → blocked.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::client::JiraClient;
+use crate::link::LinkRelation;
+use crate::models::{Issue, IssueLink};
+
+/// Fields fetched for every issue in the graph.
+pub const GRAPH_FIELDS: [&str; 3] = ["summary", "status", "issuelinks"];
+
+/// Link type whose loops are reported as cycles.
+pub const BLOCKS: &str = LinkRelation::Blocks.link_type_name();
+
+const SUMMARY_WIDTH: usize = 40;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphNode {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_category: Option<String>,
+    /// False for issues only reached through a link of a matching issue.
+    #[serde(skip)]
+    pub matched: bool,
+}
+
+impl GraphNode {
+    pub fn is_done(&self) -> bool {
+        self.status_category.as_deref() == Some("done")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    /// Link type name, e.g. `Blocks`.
+    pub link_type: String,
+    /// The outward verb, e.g. `blocks`.
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LinkGraph {
+    pub nodes: BTreeMap<String, GraphNode>,
+    pub edges: BTreeSet<GraphEdge>,
+}
+
+impl LinkGraph {
+    /// Builds the graph from issues fetched with `issuelinks`, keeping links
+    /// whose type name or outward verb matches one of `link_types`
+    /// (case-insensitive). Both ends of a link report it; they collapse into one edge.
+    pub fn from_issues(issues: &[Issue], link_types: &[String]) -> Self {
+        let mut graph = LinkGraph::default();
+        for issue in issues {
+            graph.nodes.insert(issue.key.clone(), node(issue, true));
+        }
+        for issue in issues {
+            for link in issue.fields.issuelinks.iter().flatten() {
+                if !matches_type(link, link_types) {
+                    continue;
+                }
+                let Some((from, to, other)) = directed(&issue.key, link) else {
+                    continue;
+                };
+                graph
+                    .nodes
+                    .entry(other.key.clone())
+                    .or_insert_with(|| node(other, false));
+                graph.edges.insert(GraphEdge {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    link_type: link.link_type.name.clone(),
+                    label: link.link_type.outward.clone(),
+                });
+            }
+        }
+        graph
+    }
+
+    /// Keys that `key` points to through links of `link_type`.
+    pub fn successors<'a>(&'a self, key: &str, link_type: &str) -> Vec<&'a str> {
+        self.edges
+            .iter()
+            .filter(|e| e.from == key && e.link_type.eq_ignore_ascii_case(link_type))
+            .map(|e| e.to.as_str())
+            .collect()
+    }
+
+    /// Keys pointing to `key` through links of `link_type`.
+    pub fn predecessors<'a>(&'a self, key: &str, link_type: &str) -> Vec<&'a str> {
+        self.edges
+            .iter()
+            .filter(|e| e.to == key && e.link_type.eq_ignore_ascii_case(link_type))
+            .map(|e| e.from.as_str())
+            .collect()
+    }
+
+    /// Keys that `key` stands in `relation` to; for [`LinkRelation::BlockedBy`],
+    /// the issues blocking `key`.
+    pub fn related<'a>(&'a self, key: &str, relation: LinkRelation) -> Vec<&'a str> {
+        self.edges
+            .iter()
+            .filter_map(|e| {
+                let (other, to_is_outward) = if e.from == key {
+                    (&e.to, true)
+                } else if e.to == key {
+                    (&e.from, false)
+                } else {
+                    return None;
+                };
+                (LinkRelation::from_link(&e.link_type, to_is_outward) == Some(relation))
+                    .then_some(other.as_str())
+            })
+            .collect()
+    }
+
+    /// Loops among links of `link_type`, each as a path that starts and ends
+    /// on the same (smallest) key, e.g. `[A, B, A]`.
+    pub fn cycles(&self, link_type: &str) -> Vec<Vec<String>> {
+        let mut cycles = Vec::new();
+        for component in self.strongly_connected(link_type) {
+            let start = component.iter().next().expect("components are non-empty");
+            let looped =
+                component.len() > 1 || self.successors(start, link_type).contains(&start.as_str());
+            if !looped {
+                continue;
+            }
+            let mut path = vec![start.clone()];
+            if self.path_back(start, start, &component, link_type, &mut path) {
+                cycles.push(path);
+            }
+        }
+        cycles
+    }
+
+    /// Depth-first search for a path from `at` back to `start` inside `component`.
+    fn path_back(
+        &self,
+        at: &str,
+        start: &str,
+        component: &BTreeSet<String>,
+        link_type: &str,
+        path: &mut Vec<String>,
+    ) -> bool {
+        for next in self.successors(at, link_type) {
+            if next == start {
+                path.push(start.to_string());
+                return true;
+            }
+            if component.contains(next) && !path.iter().any(|k| k == next) {
+                path.push(next.to_string());
+                if self.path_back(next, start, component, link_type, path) {
+                    return true;
+                }
+                path.pop();
+            }
+        }
+        false
+    }
+
+    /// Tarjan's algorithm over edges of `link_type`.
+    fn strongly_connected(&self, link_type: &str) -> Vec<BTreeSet<String>> {
+        struct State<'a> {
+            index: BTreeMap<&'a str, usize>,
+            low: BTreeMap<&'a str, usize>,
+            stack: Vec<&'a str>,
+            on_stack: BTreeSet<&'a str>,
+            components: Vec<BTreeSet<String>>,
+        }
+
+        fn visit<'a>(graph: &'a LinkGraph, key: &'a str, link_type: &str, s: &mut State<'a>) {
+            let index = s.index.len();
+            s.index.insert(key, index);
+            s.low.insert(key, index);
+            s.stack.push(key);
+            s.on_stack.insert(key);
+            for next in graph.successors(key, link_type) {
+                if !s.index.contains_key(next) {
+                    visit(graph, next, link_type, s);
+                    let low = s.low[key].min(s.low[next]);
+                    s.low.insert(key, low);
+                } else if s.on_stack.contains(next) {
+                    let low = s.low[key].min(s.index[next]);
+                    s.low.insert(key, low);
+                }
+            }
+            if s.low[key] == s.index[key] {
+                let mut component = BTreeSet::new();
+                while let Some(member) = s.stack.pop() {
+                    s.on_stack.remove(member);
+                    component.insert(member.to_string());
+                    if member == key {
+                        break;
+                    }
+                }
+                s.components.push(component);
+            }
+        }
+
+        let mut state = State {
+            index: BTreeMap::new(),
+            low: BTreeMap::new(),
+            stack: Vec::new(),
+            on_stack: BTreeSet::new(),
+            components: Vec::new(),
+        };
+        for key in self.nodes.keys() {
+            if !state.index.contains_key(key.as_str()) {
+                visit(self, key, link_type, &mut state);
+            }
+        }
+        state.components.sort();
+        state.components
+    }
+
+    /// Graphviz DOT; nodes are filled by status category and blocking loops drawn in red.
+    pub fn to_dot(&self) -> String {
+        let looped = self.cycle_edges();
+        let mut out = String::from("digraph jira {\n");
+        out.push_str("  rankdir=LR;\n");
+        out.push_str("  node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n");
+        for node in self.nodes.values() {
+            let mut attrs = format!(
+                "label=\"{}\", fillcolor=\"{}\"",
+                dot_escape(&label(node, "\\n")),
+                fill_color(node.status_category.as_deref())
+            );
+            if !node.matched {
+                attrs.push_str(", style=\"rounded,filled,dashed\"");
+            }
+            out.push_str(&format!("  \"{}\" [{attrs}];\n", dot_escape(&node.key)));
+        }
+        for edge in &self.edges {
+            let mut attrs = format!("label=\"{}\"", dot_escape(&edge.label));
+            if looped.contains(&(edge.from.as_str(), edge.to.as_str())) {
+                attrs.push_str(", color=\"red\", fontcolor=\"red\"");
+            }
+            out.push_str(&format!(
+                "  \"{}\" -> \"{}\" [{attrs}];\n",
+                dot_escape(&edge.from),
+                dot_escape(&edge.to)
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// A Mermaid flowchart; nodes get a class per status category.
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::from("flowchart LR\n");
+        for node in self.nodes.values() {
+            out.push_str(&format!(
+                "  {}[\"{}\"]\n",
+                mermaid_id(&node.key),
+                mermaid_escape(&label(node, ": "))
+            ));
+        }
+        for edge in &self.edges {
+            out.push_str(&format!(
+                "  {} -->|{}| {}\n",
+                mermaid_id(&edge.from),
+                mermaid_escape(&edge.label),
+                mermaid_id(&edge.to)
+            ));
+        }
+        let mut classes: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for node in self.nodes.values() {
+            let category = category_class(node.status_category.as_deref());
+            classes
+                .entry(category)
+                .or_default()
+                .push(mermaid_id(&node.key));
+        }
+        for (class, ids) in &classes {
+            out.push_str(&format!(
+                "  classDef {class} fill:{}\n",
+                fill_color(Some(class))
+            ));
+            out.push_str(&format!("  class {} {class}\n", ids.join(",")));
+        }
+        out
+    }
+
+    fn cycle_edges(&self) -> BTreeSet<(&str, &str)> {
+        let mut edges = BTreeSet::new();
+        for cycle in self.cycles(BLOCKS) {
+            for pair in cycle.windows(2) {
+                if let Some(edge) = self
+                    .edges
+                    .iter()
+                    .find(|e| e.from == pair[0] && e.to == pair[1])
+                {
+                    edges.insert((edge.from.as_str(), edge.to.as_str()));
+                }
+            }
+        }
+        edges
+    }
+}
+
+impl JiraClient {
+    /// Fetches every issue matching `jql` and builds the graph of their links.
+    pub async fn link_graph(&self, jql: &str, link_types: &[String]) -> Result<LinkGraph> {
+        let issues = self.search_all(jql, &GRAPH_FIELDS).await?;
+        Ok(LinkGraph::from_issues(&issues, link_types))
+    }
+}
+
+/// `(from, to, other)` for a link held by `holder`: an `outwardIssue` entry
+/// reads "holder <outward verb> other", an `inwardIssue` entry the reverse.
+fn directed<'a>(holder: &'a str, link: &'a IssueLink) -> Option<(&'a str, &'a str, &'a Issue)> {
+    match (&link.outward_issue, &link.inward_issue) {
+        (Some(other), _) => Some((holder, other.key.as_str(), other)),
+        (None, Some(other)) => Some((other.key.as_str(), holder, other)),
+        (None, None) => None,
+    }
+}
+
+fn matches_type(link: &IssueLink, link_types: &[String]) -> bool {
+    link_types.iter().any(|wanted| {
+        link.link_type.name.eq_ignore_ascii_case(wanted)
+            || link.link_type.outward.eq_ignore_ascii_case(wanted)
+    })
+}
+
+fn node(issue: &Issue, matched: bool) -> GraphNode {
+    GraphNode {
+        key: issue.key.clone(),
+        summary: issue.fields.summary.clone(),
+        status: issue.status_name().map(str::to_string),
+        status_category: issue.status_category().map(str::to_string),
+        matched,
+    }
+}
+
+fn label(node: &GraphNode, separator: &str) -> String {
+    match &node.summary {
+        Some(summary) if summary.chars().count() > SUMMARY_WIDTH => {
+            let short: String = summary.chars().take(SUMMARY_WIDTH - 1).collect();
+            format!("{}{separator}{short}…", node.key)
+        }
+        Some(summary) => format!("{}{separator}{summary}", node.key),
+        None => node.key.clone(),
+    }
+}
+
+fn category_class(category: Option<&str>) -> &'static str {
+    match category {
+        Some("new") => "new",
+        Some("indeterminate") => "indeterminate",
+        Some("done") => "done",
+        _ => "unknown",
+    }
+}
+
+/// Jira's own lozenge colors for To Do, In Progress and Done.
+fn fill_color(category: Option<&str>) -> &'static str {
+    match category_class(category) {
+        "new" => "#DFE1E6",
+        "indeterminate" => "#DEEBFF",
+        "done" => "#E3FCEF",
+        _ => "#FFFFFF",
+    }
+}
+
+fn dot_escape(text: &str) -> String {
+    // Keep the `\n` line breaks produced by `label`.
+    text.replace('"', "\\\"")
+}
+
+fn mermaid_id(key: &str) -> String {
+    key.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn mermaid_escape(text: &str) -> String {
+    text.replace('"', "#quot;").replace('|', "#124;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LinkRelation;
+    use serde_json::json;
+
+    fn issue(key: &str, category: &str, links: serde_json::Value) -> Issue {
+        serde_json::from_value(json!({
+            "key": key,
+            "fields": {
+                "summary": format!("Summary of {key}"),
+                "status": { "name": category, "statusCategory": { "key": category } },
+                "issuelinks": links
+            }
+        }))
+        .unwrap()
+    }
+
+    fn blocks_type() -> serde_json::Value {
+        json!({ "name": "Blocks", "inward": "is blocked by", "outward": "blocks" })
+    }
+
+    #[test]
+    fn edges_follow_the_direction_links_are_created_with() {
+        // `jico link ACME-1 --to ACME-2 --relation blocks` ...
+        let (outward, inward) = LinkRelation::Blocks.outward_inward_keys("ACME-1", "ACME-2");
+        assert_eq!((outward, inward), ("ACME-2", "ACME-1"));
+        // ... is reported on each side of the link like this.
+        let issues = [
+            issue(
+                "ACME-1",
+                "new",
+                json!([{ "type": blocks_type(), "outwardIssue": { "key": "ACME-2" } }]),
+            ),
+            issue(
+                "ACME-2",
+                "new",
+                json!([{ "type": blocks_type(), "inwardIssue": { "key": "ACME-1" } }]),
+            ),
+        ];
+
+        let graph = LinkGraph::from_issues(&issues, &["blocks".to_string()]);
+
+        assert_eq!(graph.edges.len(), 1);
+        let edge = graph.edges.iter().next().unwrap();
+        assert_eq!((edge.from.as_str(), edge.to.as_str()), ("ACME-1", "ACME-2"));
+        assert_eq!(edge.label, "blocks");
+        assert_eq!(graph.related("ACME-1", LinkRelation::Blocks), ["ACME-2"]);
+        assert_eq!(graph.related("ACME-2", LinkRelation::BlockedBy), ["ACME-1"]);
+        assert!(graph.related("ACME-2", LinkRelation::Blocks).is_empty());
+    }
+
+    #[test]
+    fn blocking_loops_are_reported_and_drawn_red() {
+        let issues = [
+            issue(
+                "ACME-1",
+                "new",
+                json!([{ "type": blocks_type(), "outwardIssue": { "key": "ACME-2" } }]),
+            ),
+            issue(
+                "ACME-2",
+                "indeterminate",
+                json!([{ "type": blocks_type(), "outwardIssue": { "key": "ACME-3" } }]),
+            ),
+            issue(
+                "ACME-3",
+                "done",
+                json!([
+                    { "type": blocks_type(), "outwardIssue": { "key": "ACME-1" } },
+                    {
+                        "type": { "name": "Relates", "inward": "relates to", "outward": "relates to" },
+                        "outwardIssue": { "key": "ACME-9", "fields": { "summary": "Elsewhere" } }
+                    }
+                ]),
+            ),
+        ];
+
+        let graph = LinkGraph::from_issues(&issues, &["Blocks".to_string()]);
+        assert_eq!(
+            graph.cycles(BLOCKS),
+            vec![vec!["ACME-1", "ACME-2", "ACME-3", "ACME-1"]]
+        );
+        assert!(!graph.nodes.contains_key("ACME-9"));
+
+        let dot = graph.to_dot();
+        assert!(dot.contains("\"ACME-3\" -> \"ACME-1\" [label=\"blocks\", color=\"red\""));
+        assert!(
+            dot.contains(
+                "\"ACME-2\" [label=\"ACME-2\\nSummary of ACME-2\", fillcolor=\"#DEEBFF\"]"
+            )
+        );
+
+        let mermaid = graph.to_mermaid();
+        assert!(mermaid.contains("  ACME_1 -->|blocks| ACME_2\n"));
+        assert!(mermaid.contains("  class ACME_3 done\n"));
+    }
+}
@@ -0,0 +1,260 @@
+//! Moving issues within the issue hierarchy: turning standard issues into
+//! sub-tasks and back, and giving issues a new parent.
+
+use anyhow::Result;
+
+use crate::client::{JiraClient, Request};
+use crate::error::{ErrorKind, JiraError};
+use crate::models::{Issue, IssueType};
+
+/// Fields needed to check where an issue may go.
+const HIERARCHY_FIELDS: &str = "issuetype,project,parent,subtasks";
+
+/// Level of an issue type: -1 for sub-tasks, 0 for standard issues, 1 for
+/// epics and higher for levels above. Falls back to the sub-task flag when
+/// Jira leaves the level out.
+pub fn hierarchy_level(issue_type: &IssueType) -> i32 {
+    issue_type
+        .hierarchy_level
+        .unwrap_or(if issue_type.subtask { -1 } else { 0 })
+}
+
+fn invalid(message: String) -> anyhow::Error {
+    JiraError::new(ErrorKind::Validation, message).into()
+}
+
+fn project_key(issue: &Issue) -> &str {
+    issue
+        .fields
+        .project
+        .as_ref()
+        .map(|p| p.key.as_str())
+        .unwrap_or_default()
+}
+
+fn type_of(issue: &Issue) -> IssueType {
+    issue.fields.issuetype.clone().unwrap_or_default()
+}
+
+impl JiraClient {
+    async fn hierarchy_info(&self, key: &str) -> Result<Issue> {
+        self.execute_as(
+            Request::get(format!("issue/{key}"))
+                .query("fields", HIERARCHY_FIELDS)
+                .action("get issue"),
+        )
+        .await
+    }
+
+    /// Turns a standard issue into a sub-task of `parent`, using `issue_type`
+    /// or the project's first sub-task type. Returns the converted issue, or
+    /// `None` under `dry_run`.
+    pub async fn convert_to_subtask(
+        &self,
+        key: &str,
+        parent: &str,
+        issue_type: Option<&str>,
+    ) -> Result<Option<Issue>> {
+        let issue = self.hierarchy_info(key).await?;
+        let parent_issue = self.hierarchy_info(parent).await?;
+        let project = project_key(&issue);
+        let current = type_of(&issue);
+        if hierarchy_level(&current) < 0 {
+            return Err(invalid(format!(
+                "{key} is already a sub-task; use reparent to change its parent"
+            )));
+        }
+        if issue
+            .fields
+            .subtasks
+            .as_ref()
+            .is_some_and(|s| !s.is_empty())
+        {
+            return Err(invalid(format!(
+                "{key} has sub-tasks of its own and cannot become a sub-task"
+            )));
+        }
+        let parent_type = type_of(&parent_issue);
+        if hierarchy_level(&parent_type) != 0 {
+            return Err(invalid(format!(
+                "{parent} is a {}; a sub-task's parent must be a standard issue",
+                parent_type.name
+            )));
+        }
+        if project_key(&parent_issue) != project {
+            return Err(invalid(format!(
+                "{parent} is in project {}, but a sub-task must stay in {project} with its parent",
+                project_key(&parent_issue)
+            )));
+        }
+
+        let types = self.issue_types(project).await?;
+        let target = match issue_type {
+            Some(name) => types
+                .into_iter()
+                .find(|t| t.name.eq_ignore_ascii_case(name))
+                .ok_or_else(|| {
+                    invalid(format!(
+                        "Issue type '{name}' is not available in project {project}"
+                    ))
+                })?,
+            None => types
+                .into_iter()
+                .find(|t| hierarchy_level(t) < 0)
+                .ok_or_else(|| invalid(format!("Project {project} has no sub-task issue type")))?,
+        };
+        if hierarchy_level(&target) >= 0 {
+            return Err(invalid(format!(
+                "'{}' is not a sub-task type in project {project}",
+                target.name
+            )));
+        }
+        self.bulk_move(key, &format!("{project},{},{parent}", target.id))
+            .await
+    }
+
+    /// Turns a sub-task into a standard issue of type `issue_type`, detached
+    /// from its parent. Returns the converted issue, or `None` under `dry_run`.
+    pub async fn convert_to_standard(&self, key: &str, issue_type: &str) -> Result<Option<Issue>> {
+        let issue = self.hierarchy_info(key).await?;
+        let project = project_key(&issue);
+        if hierarchy_level(&type_of(&issue)) >= 0 {
+            return Err(invalid(format!("{key} is not a sub-task")));
+        }
+        let target = self.issue_type(project, issue_type).await?;
+        if hierarchy_level(&target) != 0 {
+            return Err(invalid(format!(
+                "'{}' is not a standard issue type in project {project}",
+                target.name
+            )));
+        }
+        self.bulk_move(key, &format!("{project},{}", target.id))
+            .await
+    }
+
+    /// Puts `key` under `parent`, which must sit exactly one level above it:
+    /// a standard issue for sub-tasks (same project), an epic for standard
+    /// issues, and so on.
+    pub async fn reparent(&self, key: &str, parent: &str) -> Result<()> {
+        let issue = self.hierarchy_info(key).await?;
+        let parent_issue = self.hierarchy_info(parent).await?;
+        let child_type = type_of(&issue);
+        let parent_type = type_of(&parent_issue);
+        if hierarchy_level(&parent_type) != hierarchy_level(&child_type) + 1 {
+            return Err(invalid(format!(
+                "{parent} ({}) cannot be the parent of {key} ({})",
+                parent_type.name, child_type.name
+            )));
+        }
+        if hierarchy_level(&child_type) >= 0 {
+            self.set_parent(key, Some(parent)).await?;
+            return Ok(());
+        }
+        // Jira only changes a sub-task's parent through a move.
+        let project = project_key(&issue);
+        if project_key(&parent_issue) != project {
+            return Err(invalid(format!(
+                "{parent} is in project {}, but sub-task {key} must stay in {project}",
+                project_key(&parent_issue)
+            )));
+        }
+        self.bulk_move(key, &format!("{project},{},{parent}", child_type.id))
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_client;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    fn mock_issue(server: &MockServer, key: &str, issue_type: serde_json::Value) {
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/rest/api/3/issue/{key}"))
+                .query_param("fields", HIERARCHY_FIELDS);
+            then.status(200).json_body(json!({
+                "key": key,
+                "fields": { "issuetype": issue_type, "project": { "key": "ACME" } }
+            }));
+        });
+    }
+
+    #[tokio::test]
+    async fn convert_to_subtask_moves_under_the_parent_with_a_subtask_type() {
+        let server = MockServer::start();
+        mock_issue(
+            &server,
+            "ACME-5",
+            json!({ "id": "1", "name": "Task", "hierarchyLevel": 0 }),
+        );
+        mock_issue(
+            &server,
+            "ACME-1",
+            json!({ "id": "2", "name": "Story", "hierarchyLevel": 0 }),
+        );
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/api/3/issue/createmeta/ACME/issuetypes");
+            then.status(200).json_body(json!({
+                "issueTypes": [
+                    { "id": "1", "name": "Task", "subtask": false },
+                    { "id": "9", "name": "Sub-task", "subtask": true }
+                ],
+                "total": 2
+            }));
+        });
+        let moved = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/bulk/issues/move")
+                .json_body_partial(
+                    r#"{ "targetToSourcesMapping": { "ACME,9,ACME-1": { "issueIdsOrKeys": ["ACME-5"] } } }"#,
+                );
+            then.status(201).json_body(json!({ "taskId": "3" }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/bulk/queue/3");
+            then.status(200).json_body(json!({ "status": "COMPLETE" }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-5");
+            then.status(200).json_body(json!({ "key": "ACME-5" }));
+        });
+
+        let issue = test_client(&server)
+            .convert_to_subtask("ACME-5", "ACME-1", None)
+            .await
+            .unwrap();
+
+        moved.assert();
+        assert_eq!(issue.unwrap().key, "ACME-5");
+    }
+
+    #[tokio::test]
+    async fn reparent_refuses_a_parent_on_the_wrong_level() {
+        let server = MockServer::start();
+        mock_issue(
+            &server,
+            "ACME-5",
+            json!({ "id": "1", "name": "Task", "hierarchyLevel": 0 }),
+        );
+        mock_issue(
+            &server,
+            "ACME-6",
+            json!({ "id": "1", "name": "Task", "hierarchyLevel": 0 }),
+        );
+
+        let err = test_client(&server)
+            .reparent("ACME-5", "ACME-6")
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "ACME-6 (Task) cannot be the parent of ACME-5 (Task)"
+        );
+    }
+}
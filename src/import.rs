@@ -0,0 +1,424 @@
+//! Bulk issue creation from rows read out of CSV, JSON or YAML files.
+//!
+//! A row may name its parent by another row's local `id`; rows are created in
+//! waves so that parents exist (and have keys) before their sub-tasks are sent.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Result;
+use reqwest::StatusCode;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value, json};
+
+use crate::client::{JiraClient, NewIssue, Request};
+use crate::error::{ErrorKind, JiraError};
+use crate::models::Issue;
+
+/// Issues per `/issue/bulk` call; Jira rejects larger batches.
+pub const BULK_CREATE_LIMIT: usize = 50;
+
+/// One issue to create. Keys that are not modeled here are sent as raw Jira
+/// fields, e.g. `customfield_10016: 5`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ImportRow {
+    /// Local id other rows can use as `parent`.
+    #[serde(default, deserialize_with = "string_or_number")]
+    pub id: Option<String>,
+    pub summary: String,
+    #[serde(default, alias = "type", alias = "issuetype")]
+    pub issue_type: Option<String>,
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// A local row id or an existing issue key.
+    #[serde(default, deserialize_with = "string_or_number")]
+    pub parent: Option<String>,
+    #[serde(default, deserialize_with = "list_or_csv")]
+    pub labels: Option<Vec<String>>,
+    #[serde(default)]
+    pub priority: Option<String>,
+    /// Account id.
+    #[serde(default)]
+    pub assignee: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// What happened to one row; `row` is 1-based in file order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RowOutcome {
+    pub row: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BulkCreateResponse {
+    #[serde(default)]
+    issues: Vec<Issue>,
+    #[serde(default)]
+    errors: Vec<BulkCreateError>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BulkCreateError {
+    #[serde(default)]
+    status: Option<u16>,
+    #[serde(default)]
+    element_errors: Value,
+    failed_element_number: usize,
+}
+
+impl ImportRow {
+    /// The `fields` object for this row once its parent key is known.
+    fn fields(
+        &self,
+        default_project: Option<&str>,
+        parent: Option<&str>,
+    ) -> Result<Map<String, Value>> {
+        let project = self.project.as_deref().or(default_project).ok_or_else(|| {
+            JiraError::new(
+                ErrorKind::Validation,
+                "no project (set a project column or pass --project)",
+            )
+        })?;
+        // Same default as `jico create`: a row with a parent is a sub-task.
+        let default_type = if self.parent.is_some() {
+            "Sub-task"
+        } else {
+            "Task"
+        };
+        let issue_type = self.issue_type.as_deref().unwrap_or(default_type);
+        let issue = NewIssue {
+            description: self.description.clone(),
+            parent: parent.map(str::to_string),
+            labels: self.labels.clone(),
+            priority: self.priority.clone(),
+            assignee: self.assignee.clone(),
+            ..NewIssue::new(project, &self.summary, issue_type)
+        };
+        let mut fields = issue.fields();
+        fields.extend(self.extra.clone());
+        Ok(fields)
+    }
+}
+
+impl JiraClient {
+    /// Creates up to [`BULK_CREATE_LIMIT`] issues in one call, returning each
+    /// one's created issue or error message in input order. Under `dry_run`
+    /// nothing is created and every issue comes back empty.
+    pub async fn create_issues(
+        &self,
+        issues: Vec<Map<String, Value>>,
+    ) -> Result<Vec<Result<Issue, String>>> {
+        let count = issues.len();
+        let updates: Vec<Value> = issues
+            .into_iter()
+            .map(|fields| json!({ "fields": fields }))
+            .collect();
+        let request =
+            Request::post("issue/bulk", json!({ "issueUpdates": updates })).action("bulk create");
+        let (status, body) = self.fetch(&request).await?;
+        if self.dry_run {
+            return Ok((0..count).map(|_| Ok(Issue::default())).collect());
+        }
+        // Jira answers 201 when every issue was created and 400 when none were;
+        // both carry the per-element `errors` list.
+        let parsed = match status {
+            StatusCode::CREATED | StatusCode::OK | StatusCode::BAD_REQUEST => {
+                serde_json::from_slice::<BulkCreateResponse>(&body).ok()
+            }
+            _ => None,
+        };
+        let Some(response) = parsed else {
+            return Err(JiraError::from_body(status, &body).into());
+        };
+
+        let mut failures: HashMap<usize, String> = HashMap::new();
+        for error in response.errors {
+            let status = error
+                .status
+                .and_then(|s| StatusCode::from_u16(s).ok())
+                .unwrap_or(StatusCode::BAD_REQUEST);
+            let details = JiraError::from_response(status, &error.element_errors).details();
+            let message = if details.is_empty() {
+                format!("rejected with status {status}")
+            } else {
+                details.join("; ")
+            };
+            failures.insert(error.failed_element_number, message);
+        }
+        // Created issues are listed in input order, skipping the failed elements.
+        let mut created = response.issues.into_iter();
+        Ok((0..count)
+            .map(|i| match failures.remove(&i) {
+                Some(message) => Err(message),
+                None => created
+                    .next()
+                    .ok_or_else(|| "missing from Jira's response".to_string()),
+            })
+            .collect())
+    }
+
+    /// Creates every row, parents before the rows that reference them by id.
+    ///
+    /// `previous` holds outcomes of an earlier run by 1-based row number: those
+    /// rows are not sent again, and the created ones still serve as parents.
+    /// `on_progress` sees each new outcome as soon as its batch is done.
+    ///
+    /// Under `dry_run` each row gets the placeholder key `(row N)`, so the
+    /// requests for rows that name it as parent still print.
+    pub async fn import_rows(
+        &self,
+        rows: &[ImportRow],
+        default_project: Option<&str>,
+        previous: &BTreeMap<usize, Result<String, String>>,
+        mut on_progress: impl FnMut(usize, &Result<String, String>),
+    ) -> Result<Vec<RowOutcome>> {
+        let mut ids: HashMap<&str, usize> = HashMap::new();
+        for (index, row) in rows.iter().enumerate() {
+            if let Some(id) = row.id.as_deref()
+                && ids.insert(id, index).is_some()
+            {
+                return Err(JiraError::new(
+                    ErrorKind::Validation,
+                    format!("Row id '{id}' is used more than once"),
+                )
+                .into());
+            }
+        }
+
+        let mut results: Vec<Option<Result<String, String>>> = vec![None; rows.len()];
+        for (&row, result) in previous {
+            if let Some(slot) = row.checked_sub(1).and_then(|index| results.get_mut(index)) {
+                *slot = Some(result.clone());
+            }
+        }
+        let mut reported: Vec<bool> = results.iter().map(Option::is_some).collect();
+        let mut report = |results: &[Option<Result<String, String>>]| {
+            for (index, result) in results.iter().enumerate() {
+                if let Some(result) = result
+                    && !reported[index]
+                {
+                    reported[index] = true;
+                    on_progress(index + 1, result);
+                }
+            }
+        };
+        loop {
+            // Rows whose parent, if local, has been created; fail those whose parent failed.
+            let mut wave = Vec::new();
+            for (index, row) in rows.iter().enumerate() {
+                if results[index].is_some() {
+                    continue;
+                }
+                let local_parent = row.parent.as_deref().and_then(|p| ids.get(p).copied());
+                match local_parent.map(|parent| &results[parent]) {
+                    None => wave.push((index, row.parent.clone())),
+                    Some(Some(Ok(key))) => wave.push((index, Some(key.clone()))),
+                    Some(Some(Err(_))) => {
+                        let parent = row.parent.as_deref().unwrap_or_default();
+                        results[index] =
+                            Some(Err(format!("parent row '{parent}' was not created")));
+                    }
+                    Some(None) => {}
+                }
+            }
+            if wave.is_empty() {
+                break;
+            }
+
+            let mut batch = Vec::new();
+            for (index, parent) in wave {
+                match rows[index].fields(default_project, parent.as_deref()) {
+                    Ok(fields) => batch.push((index, fields)),
+                    Err(err) => results[index] = Some(Err(err.to_string())),
+                }
+            }
+            for chunk in batch.chunks(BULK_CREATE_LIMIT) {
+                let fields = chunk.iter().map(|(_, fields)| fields.clone()).collect();
+                let created = self.create_issues(fields).await?;
+                for ((index, _), outcome) in chunk.iter().zip(created) {
+                    let key = |issue: Issue| {
+                        if self.dry_run {
+                            format!("(row {})", index + 1)
+                        } else {
+                            issue.key
+                        }
+                    };
+                    results[*index] = Some(outcome.map(key));
+                }
+                report(&results);
+            }
+            report(&results);
+        }
+
+        for result in results.iter_mut().filter(|result| result.is_none()) {
+            *result = Some(Err("parent rows reference each other in a loop".to_string()));
+        }
+        report(&results);
+
+        Ok(rows
+            .iter()
+            .zip(results)
+            .enumerate()
+            .map(|(index, (row, result))| {
+                let result = result.expect("every row has an outcome");
+                RowOutcome {
+                    row: index + 1,
+                    id: row.id.clone(),
+                    summary: row.summary.clone(),
+                    key: result.as_ref().ok().cloned(),
+                    error: result.err(),
+                }
+            })
+            .collect())
+    }
+}
+
+/// Accepts `id: 3` as well as `id: "3"`; empty strings count as absent.
+fn string_or_number<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(s)) if s.is_empty() => None,
+        Some(Value::String(s)) => Some(s),
+        Some(Value::Number(n)) => Some(n.to_string()),
+        Some(Value::Null) | None => None,
+        Some(other) => {
+            return Err(serde::de::Error::custom(format!(
+                "expected a string or number, got {other}"
+            )));
+        }
+    })
+}
+
+/// Accepts a list or a comma-separated string such as a CSV cell.
+fn list_or_csv<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Vec<String>>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Labels {
+        List(Vec<String>),
+        Text(String),
+    }
+    Ok(
+        Option::<Labels>::deserialize(deserializer)?.map(|labels| match labels {
+            Labels::List(list) => list,
+            Labels::Text(text) => text
+                .split(',')
+                .map(str::trim)
+                .filter(|label| !label.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_client;
+    use httpmock::prelude::*;
+
+    fn rows(value: Value) -> Vec<ImportRow> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn rows_accept_numeric_ids_label_strings_and_raw_fields() {
+        let row = &rows(json!([{
+            "id": 1,
+            "summary": "Login",
+            "type": "Story",
+            "labels": "auth, web",
+            "customfield_10016": 5
+        }]))[0];
+
+        assert_eq!(row.id.as_deref(), Some("1"));
+        assert_eq!(row.issue_type.as_deref(), Some("Story"));
+        assert_eq!(
+            row.labels,
+            Some(vec!["auth".to_string(), "web".to_string()])
+        );
+        let fields = row.fields(Some("ACME"), None).unwrap();
+        assert_eq!(fields["customfield_10016"], 5);
+        assert_eq!(fields["project"], json!({ "key": "ACME" }));
+    }
+
+    #[tokio::test]
+    async fn import_creates_parents_first_and_reports_each_row() {
+        let server = MockServer::start();
+        let parents = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/issue/bulk")
+                .json_body_partial(
+                    r#"{ "issueUpdates": [
+                    { "fields": { "summary": "Story" } },
+                    { "fields": { "summary": "Broken" } }
+                ] }"#,
+                );
+            then.status(201).json_body(json!({
+                "issues": [{ "id": "10", "key": "ACME-10" }],
+                "errors": [{
+                    "status": 400,
+                    "elementErrors": { "errors": { "issuetype": "invalid" } },
+                    "failedElementNumber": 1
+                }]
+            }));
+        });
+        let children = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/issue/bulk")
+                .json_body_partial(
+                    r#"{ "issueUpdates": [
+                    { "fields": { "summary": "Sub-task", "parent": { "key": "ACME-10" } } }
+                ] }"#,
+                );
+            then.status(201)
+                .json_body(json!({ "issues": [{ "id": "11", "key": "ACME-11" }], "errors": [] }));
+        });
+
+        let outcomes = test_client(&server)
+            .import_rows(
+                &rows(json!([
+                    { "id": "s1", "summary": "Story", "type": "Story" },
+                    { "summary": "Sub-task", "type": "Sub-task", "parent": "s1" },
+                    { "id": "b", "summary": "Broken", "type": "Nope" },
+                    { "summary": "Orphan", "parent": "b" }
+                ])),
+                Some("ACME"),
+                &BTreeMap::new(),
+                |_, _| {},
+            )
+            .await
+            .unwrap();
+
+        parents.assert();
+        children.assert();
+        let summary: Vec<(Option<&str>, Option<&str>)> = outcomes
+            .iter()
+            .map(|o| (o.key.as_deref(), o.error.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (Some("ACME-10"), None),
+                (Some("ACME-11"), None),
+                (None, Some("issuetype: invalid")),
+                (None, Some("parent row 'b' was not created")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,572 @@
+//! A local log of the changes jico makes to issues, holding enough of the
+//! previous state to put them back.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::client::{JiraClient, Request, writable};
+use crate::error::{ErrorKind, JiraError};
+use crate::link::LinkRelation;
+
+/// One change to one issue, as recorded before (or, for links, right after) it was made.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "camelCase")]
+pub enum Change {
+    /// Field values of `key` before an update, ready to be sent back.
+    Fields {
+        key: String,
+        before: Map<String, Value>,
+    },
+    /// `key` left status `from` through the transition named `transition`.
+    Transition {
+        key: String,
+        from: String,
+        transition: String,
+    },
+    /// A link created from `key` to `to`.
+    Link {
+        key: String,
+        to: String,
+        relation: LinkRelation,
+    },
+    /// A link from `key` to `to` that was removed.
+    Unlink {
+        key: String,
+        to: String,
+        relation: LinkRelation,
+    },
+    /// A change jico cannot revert, such as a delete or a move, kept so that
+    /// `history` shows it; `action` describes it, e.g. `delete`.
+    Irreversible { key: String, action: String },
+}
+
+impl Change {
+    pub fn key(&self) -> &str {
+        match self {
+            Change::Fields { key, .. }
+            | Change::Transition { key, .. }
+            | Change::Link { key, .. }
+            | Change::Unlink { key, .. }
+            | Change::Irreversible { key, .. } => key,
+        }
+    }
+
+    /// Whether `undo` can put this change back.
+    pub fn is_revertible(&self) -> bool {
+        !matches!(self, Change::Irreversible { .. })
+    }
+}
+
+/// A line of the journal file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntry {
+    pub operation: u64,
+    /// RFC 3339 time the change was recorded.
+    pub at: String,
+    pub command: String,
+    /// The operation this one reverted, for entries written by `undo`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub undoes: Option<u64>,
+    #[serde(flatten)]
+    pub change: Change,
+}
+
+/// The changes made by one jico invocation.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Operation {
+    pub id: u64,
+    pub at: String,
+    pub command: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub undoes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub undone_by: Option<u64>,
+    pub changes: Vec<Change>,
+}
+
+/// What undoing one change did; `error` is unset on success.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoOutcome {
+    pub key: String,
+    pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// An append-only JSON Lines file; every entry written through one `Journal`
+/// belongs to the same operation.
+#[derive(Debug)]
+pub struct Journal {
+    path: PathBuf,
+    command: String,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Assigned on the first write, so read-only runs leave no trace.
+    operation: Option<u64>,
+    undoes: Option<u64>,
+}
+
+/// `$XDG_STATE_HOME/jico/journal.jsonl`, falling back to `~/.local/state`
+/// and then `%LOCALAPPDATA%`.
+pub fn default_journal_path() -> Option<PathBuf> {
+    let env_dir = |name: &str| {
+        std::env::var_os(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+    env_dir("XDG_STATE_HOME")
+        .or_else(|| env_dir("HOME").map(|home| home.join(".local").join("state")))
+        .or_else(|| env_dir("LOCALAPPDATA"))
+        .map(|dir| dir.join("jico").join("journal.jsonl"))
+}
+
+impl Journal {
+    /// `command` is stored with every entry to tell operations apart in `history`.
+    pub fn new(path: impl Into<PathBuf>, command: &str) -> Self {
+        Self {
+            path: path.into(),
+            command: command.to_string(),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Every entry in file order; a missing file is an empty journal.
+    pub fn entries(&self) -> Result<Vec<JournalEntry>> {
+        let text = match std::fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("Failed to read journal {}", self.path.display()));
+            }
+        };
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).map_err(|err| {
+                    JiraError::new(
+                        ErrorKind::Validation,
+                        format!(
+                            "{}:{} is not a jico journal entry: {err}",
+                            self.path.display(),
+                            index + 1
+                        ),
+                    )
+                    .into()
+                })
+            })
+            .collect()
+    }
+
+    /// Recorded operations, oldest first.
+    pub fn operations(&self) -> Result<Vec<Operation>> {
+        let mut operations: BTreeMap<u64, Operation> = BTreeMap::new();
+        for entry in self.entries()? {
+            operations
+                .entry(entry.operation)
+                .or_insert_with(|| Operation {
+                    id: entry.operation,
+                    at: entry.at,
+                    command: entry.command,
+                    undoes: entry.undoes,
+                    undone_by: None,
+                    changes: Vec::new(),
+                })
+                .changes
+                .push(entry.change);
+        }
+        let undone: Vec<(u64, u64)> = operations
+            .values()
+            .filter_map(|op| op.undoes.map(|target| (target, op.id)))
+            .collect();
+        for (target, by) in undone {
+            if let Some(op) = operations.get_mut(&target) {
+                op.undone_by = Some(by);
+            }
+        }
+        Ok(operations.into_values().collect())
+    }
+
+    /// The operation with this id, or the latest one that is neither undone
+    /// nor an undo itself and has a change to revert; refuses operations that
+    /// were already undone.
+    pub fn undoable(&self, id: Option<u64>) -> Result<Operation> {
+        let operations = self.operations()?;
+        let operation = match id {
+            Some(id) => operations
+                .into_iter()
+                .find(|op| op.id == id)
+                .ok_or_else(|| {
+                    JiraError::new(
+                        ErrorKind::Validation,
+                        format!("No operation {id} in {}", self.path.display()),
+                    )
+                })?,
+            None => operations
+                .into_iter()
+                .rev()
+                .find(|op| {
+                    op.undone_by.is_none()
+                        && op.undoes.is_none()
+                        && op.changes.iter().any(Change::is_revertible)
+                })
+                .ok_or_else(|| {
+                    JiraError::new(
+                        ErrorKind::Validation,
+                        format!("Nothing to undo in {}", self.path.display()),
+                    )
+                })?,
+        };
+        if let Some(by) = operation.undone_by {
+            return Err(JiraError::new(
+                ErrorKind::Validation,
+                format!(
+                    "Operation {} was already undone by operation {by}",
+                    operation.id
+                ),
+            )
+            .into());
+        }
+        Ok(operation)
+    }
+
+    /// Starts a new operation whose entries are marked as reverting `operation`.
+    pub fn set_undoes(&self, operation: u64) {
+        let mut state = self.lock();
+        state.operation = None;
+        state.undoes = Some(operation);
+    }
+
+    pub fn record(&self, change: Change) -> Result<()> {
+        let mut state = self.lock();
+        let operation = match state.operation {
+            Some(operation) => operation,
+            None => {
+                let last = self.entries()?.iter().map(|e| e.operation).max();
+                *state.operation.insert(last.map_or(1, |last| last + 1))
+            }
+        };
+        let entry = JournalEntry {
+            operation,
+            at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            command: self.command.clone(),
+            undoes: state.undoes,
+            change,
+        };
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .with_context(|| format!("Failed to write journal {}", self.path.display()))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl JiraClient {
+    /// The journal to write to; nothing is recorded under `dry_run`.
+    fn active_journal(&self) -> Option<&Journal> {
+        self.journal.as_ref().filter(|_| !self.dry_run)
+    }
+
+    /// Reads the current values of `names` on `key` when journaling.
+    pub(crate) async fn snapshot_fields(
+        &self,
+        key: &str,
+        names: &[&str],
+    ) -> Result<Option<Change>> {
+        if self.active_journal().is_none() || names.is_empty() {
+            return Ok(None);
+        }
+        let issue = self
+            .execute(
+                Request::get(format!("issue/{key}"))
+                    .query("fields", names.join(","))
+                    .action("snapshot issue"),
+            )
+            .await?;
+        let before = names
+            .iter()
+            .map(|name| {
+                let value = issue["fields"].get(*name).unwrap_or(&Value::Null);
+                (name.to_string(), writable(value))
+            })
+            .collect();
+        Ok(Some(Change::Fields {
+            key: key.to_string(),
+            before,
+        }))
+    }
+
+    /// Reads the current status of `key` when journaling.
+    pub(crate) async fn snapshot_status(
+        &self,
+        key: &str,
+        transition: &str,
+    ) -> Result<Option<Change>> {
+        if self.active_journal().is_none() {
+            return Ok(None);
+        }
+        let issue = self
+            .execute(
+                Request::get(format!("issue/{key}"))
+                    .query("fields", "status")
+                    .action("snapshot issue"),
+            )
+            .await?;
+        let from = issue["fields"]["status"]["name"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        Ok(Some(Change::Transition {
+            key: key.to_string(),
+            from,
+            transition: transition.to_string(),
+        }))
+    }
+
+    /// Appends a change taken by one of the snapshot helpers, once the mutation went through.
+    pub(crate) fn record(&self, change: Option<Change>) -> Result<()> {
+        match (self.active_journal(), change) {
+            (Some(journal), Some(change)) => journal.record(change),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn journaling(&self) -> bool {
+        self.active_journal().is_some()
+    }
+
+    /// Reverts an operation's changes, newest first. The reverting changes are
+    /// journaled as a new operation, so an undo can itself be undone.
+    pub async fn undo(&self, operation: &Operation) -> Vec<UndoOutcome> {
+        if let Some(journal) = self.active_journal() {
+            journal.set_undoes(operation.id);
+        }
+        let mut outcomes = Vec::with_capacity(operation.changes.len());
+        for change in operation.changes.iter().rev() {
+            let (action, result) = match change {
+                Change::Fields { key, before } => {
+                    let names: Vec<&str> = before.keys().map(String::as_str).collect();
+                    (
+                        format!("restore {}", names.join(", ")),
+                        self.update_issue(key, before.clone()).await.map(drop),
+                    )
+                }
+                Change::Transition { key, from, .. } => (
+                    format!("move back to {from}"),
+                    self.transition_back(key, from).await,
+                ),
+                Change::Link { key, to, relation } => (
+                    format!("remove link to {to}"),
+                    self.unlink_issues(key, to, *relation).await,
+                ),
+                Change::Unlink { key, to, relation } => (
+                    format!("restore link to {to}"),
+                    self.link_issues(key, to, *relation).await.map(drop),
+                ),
+                Change::Irreversible { action, .. } => (
+                    format!("revert {action}"),
+                    Err(JiraError::new(
+                        ErrorKind::Validation,
+                        format!(
+                            "a {action} cannot be undone; only field updates, transitions and links can"
+                        ),
+                    )
+                    .into()),
+                ),
+            };
+            outcomes.push(UndoOutcome {
+                key: change.key().to_string(),
+                action,
+                error: result.err().map(|err| format!("{err:#}")),
+            });
+        }
+        outcomes
+    }
+
+    /// Runs the transition that leads to status `status`, when the workflow has one.
+    async fn transition_back(&self, key: &str, status: &str) -> Result<()> {
+        let transitions = self.get_transitions(key).await?;
+        let transition = transitions
+            .iter()
+            .find(|t| {
+                t.to.as_ref()
+                    .is_some_and(|to| to.name.eq_ignore_ascii_case(status))
+            })
+            .ok_or_else(|| {
+                JiraError::new(
+                    ErrorKind::Validation,
+                    format!("No transition leads {key} back to '{status}'"),
+                )
+            })?;
+        self.transition_issue(key, &transition.name).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_client;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    fn client(server: &MockServer, journal: &Path) -> JiraClient {
+        let mut client = test_client(server);
+        client.journal = Some(Journal::new(journal, "jico test"));
+        client
+    }
+
+    fn journal_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("jico-journal-{name}-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[tokio::test]
+    async fn update_is_journaled_and_undo_restores_the_old_values() {
+        let server = MockServer::start();
+        let path = journal_path("update");
+        let snapshot = server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/api/3/issue/ACME-1")
+                .query_param("fields", "priority,summary");
+            then.status(200).json_body(json!({
+                "key": "ACME-1",
+                "fields": { "summary": "Old", "priority": { "id": "3", "name": "Medium" } }
+            }));
+        });
+        let update = server.mock(|when, then| {
+            when.method(PUT).path("/rest/api/3/issue/ACME-1").json_body(
+                json!({ "fields": { "priority": { "name": "High" }, "summary": "New" } }),
+            );
+            then.status(204);
+        });
+        let restore = server.mock(|when, then| {
+            when.method(PUT)
+                .path("/rest/api/3/issue/ACME-1")
+                .json_body(json!({ "fields": { "priority": { "id": "3" }, "summary": "Old" } }));
+            then.status(204);
+        });
+
+        let mut fields = Map::new();
+        fields.insert("summary".to_string(), json!("New"));
+        fields.insert("priority".to_string(), json!({ "name": "High" }));
+        let client = client(&server, &path);
+        client.update_issue("ACME-1", fields).await.unwrap();
+        update.assert();
+
+        let operation = client.journal.as_ref().unwrap().undoable(None).unwrap();
+        assert_eq!(operation.id, 1);
+        let outcomes = client.undo(&operation).await;
+        restore.assert();
+        snapshot.assert_hits(2);
+        assert!(outcomes[0].error.is_none(), "{outcomes:?}");
+        assert_eq!(outcomes[0].action, "restore priority, summary");
+
+        let operations = client.journal.as_ref().unwrap().operations().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(operations.len(), 2);
+        assert_eq!(operations[0].undone_by, Some(2));
+        assert_eq!(operations[1].undoes, Some(1));
+    }
+
+    #[tokio::test]
+    async fn deletes_are_listed_but_passed_over_by_undo() {
+        let server = MockServer::start();
+        let path = journal_path("delete");
+        server.mock(|when, then| {
+            when.method(DELETE).path("/rest/api/3/issue/ACME-2");
+            then.status(204);
+        });
+        Journal::new(&path, "jico transition")
+            .record(Change::Transition {
+                key: "ACME-1".to_string(),
+                from: "To Do".to_string(),
+                transition: "Start".to_string(),
+            })
+            .unwrap();
+        let client = client(&server, &path);
+        client.delete_issue("ACME-2", false).await.unwrap();
+
+        let journal = client.journal.as_ref().unwrap();
+        let latest = journal.undoable(None).unwrap();
+        let delete = journal.undoable(Some(2)).unwrap();
+        let outcomes = client.undo(&delete).await;
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(latest.id, 1);
+        assert_eq!(
+            delete.changes,
+            [Change::Irreversible {
+                key: "ACME-2".to_string(),
+                action: "delete".to_string(),
+            }]
+        );
+        assert_eq!(outcomes[0].action, "revert delete");
+        assert!(
+            outcomes[0]
+                .error
+                .as_deref()
+                .unwrap()
+                .contains("cannot be undone")
+        );
+    }
+
+    #[tokio::test]
+    async fn undo_refuses_an_operation_already_undone() {
+        let path = journal_path("undone");
+        let change = |key: &str| Change::Transition {
+            key: key.to_string(),
+            from: "To Do".to_string(),
+            transition: "Start".to_string(),
+        };
+        Journal::new(&path, "jico transition")
+            .record(change("ACME-1"))
+            .unwrap();
+        let undo = Journal::new(&path, "jico undo");
+        undo.set_undoes(1);
+        undo.record(change("ACME-1")).unwrap();
+
+        let journal = Journal::new(&path, "jico undo");
+        let err = journal.undoable(Some(1)).unwrap_err();
+        let nothing = journal.undoable(None).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            err.to_string(),
+            "Operation 1 was already undone by operation 2"
+        );
+        assert!(nothing.to_string().starts_with("Nothing to undo"));
+    }
+}
@@ -0,0 +1,66 @@
+//! Client library behind the `jico` CLI for Jira Cloud.
+//!
+//! ```no_run
+//! # async fn demo() -> anyhow::Result<()> {
+//! use jico::{JiraClient, Settings};
+//!
+//! let settings = Settings::new("https://acme.atlassian.net", "dev@acme.io", "token");
+//! let client = JiraClient::new(&settings)?;
+//! let issue = client.get_issue("ACME-1").await?;
+//! println!("{} is {:?}", issue.key, issue.status_name());
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod adf;
+pub mod agile;
+pub mod bulk;
+pub mod changelog;
+pub mod checkpoint;
+pub mod client;
+pub mod clone;
+pub mod cycle_time;
+pub mod dependency;
+pub mod epic;
+pub mod error;
+pub mod flow;
+pub mod graph;
+pub mod hierarchy;
+pub mod import;
+pub mod journal;
+pub mod link;
+pub mod merge;
+pub mod meta;
+pub mod models;
+pub mod retry;
+pub mod settings;
+pub mod standup;
+pub mod timesheet;
+pub mod tree;
+pub mod velocity;
+
+pub use adf::{adf_to_text, description_to_adf, prepend_note};
+pub use agile::{CarryOver, NewSprint, SprintCloseReport};
+pub use bulk::{BulkOperation, BulkOutcome};
+pub use changelog::{ChangeItem, ChangeSet, TimelineEvent};
+pub use checkpoint::{BulkCheckpoint, Checkpoint, ImportCheckpoint};
+pub use client::{Api, JiraClient, NewIssue, Request};
+pub use clone::{CloneOptions, CloneReport};
+pub use cycle_time::{CycleTimeOptions, CycleTimeReport};
+pub use dependency::{Blocker, CriticalPath};
+pub use epic::EpicProgress;
+pub use error::{ErrorKind, JiraError};
+pub use flow::{CumulativeFlow, FlowPoint, Interval};
+pub use graph::{GraphEdge, GraphNode, LinkGraph};
+pub use import::{ImportRow, RowOutcome};
+pub use journal::{Change, Journal, JournalEntry, Operation, UndoOutcome};
+pub use link::LinkRelation;
+pub use merge::MergeReport;
+pub use meta::CreateField;
+pub use models::{Board, Comment, Fields, Issue, SearchResults, Sprint, Transition, User, Worklog};
+pub use retry::RetryPolicy;
+pub use settings::Settings;
+pub use standup::{Activity, OpenWork, Standup};
+pub use timesheet::{GroupBy, Timesheet, TimesheetRow};
+pub use tree::{IssueTree, TreeOptions};
+pub use velocity::{Burndown, BurndownDay, SprintVelocity};
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+/// Direction-aware issue link relations, named from the point of view of the first issue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum LinkRelation {
+    /// key blocks --to
+    Blocks,
+    /// key is blocked by --to
+    BlockedBy,
+    /// key clones --to
+    Clones,
+    /// key is cloned by --to
+    IsClonedBy,
+    /// key duplicates --to
+    Duplicates,
+    /// key is duplicated by --to
+    IsDuplicatedBy,
+    /// key relates to --to
+    RelatesTo,
+}
+
+impl LinkRelation {
+    pub const fn link_type_name(self) -> &'static str {
+        match self {
+            LinkRelation::Blocks | LinkRelation::BlockedBy => "Blocks",
+            LinkRelation::Clones | LinkRelation::IsClonedBy => "Cloners",
+            LinkRelation::Duplicates | LinkRelation::IsDuplicatedBy => "Duplicate",
+            LinkRelation::RelatesTo => "Relates",
+        }
+    }
+
+    /// The relation a link of type `type_name` expresses from one issue to
+    /// `to`, given which side `to` is on; `None` for other link types.
+    pub fn from_link(type_name: &str, to_is_outward: bool) -> Option<Self> {
+        let relation = match (type_name.to_ascii_lowercase().as_str(), to_is_outward) {
+            ("blocks", true) => LinkRelation::Blocks,
+            ("blocks", false) => LinkRelation::BlockedBy,
+            ("cloners", true) => LinkRelation::Clones,
+            ("cloners", false) => LinkRelation::IsClonedBy,
+            ("duplicate", true) => LinkRelation::Duplicates,
+            ("duplicate", false) => LinkRelation::IsDuplicatedBy,
+            // Relates reads the same both ways.
+            ("relates", _) => LinkRelation::RelatesTo,
+            _ => return None,
+        };
+        Some(relation)
+    }
+
+    pub fn outward_inward_keys<'a>(self, key: &'a str, to: &'a str) -> (&'a str, &'a str) {
+        match self {
+            // Jira renders links as:
+            // - current issue == inwardIssue  -> type.outward  ("blocks")
+            // - current issue == outwardIssue -> type.inward   ("is blocked by")
+            LinkRelation::Blocks
+            | LinkRelation::Clones
+            | LinkRelation::Duplicates
+            | LinkRelation::RelatesTo => (to, key),
+            LinkRelation::BlockedBy | LinkRelation::IsClonedBy | LinkRelation::IsDuplicatedBy => {
+                (key, to)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_relation_maps_type_name_and_direction() {
+        let key = "MG-26";
+        let to = "MG-3";
+        let cases = [
+            (LinkRelation::Blocks, "Blocks", ("MG-3", "MG-26")),
+            (LinkRelation::BlockedBy, "Blocks", ("MG-26", "MG-3")),
+            (LinkRelation::Clones, "Cloners", ("MG-3", "MG-26")),
+            (LinkRelation::IsClonedBy, "Cloners", ("MG-26", "MG-3")),
+            (LinkRelation::Duplicates, "Duplicate", ("MG-3", "MG-26")),
+            (LinkRelation::IsDuplicatedBy, "Duplicate", ("MG-26", "MG-3")),
+            (LinkRelation::RelatesTo, "Relates", ("MG-3", "MG-26")),
+        ];
+
+        for (relation, expected_type, (expected_outward, expected_inward)) in cases {
+            assert_eq!(relation.link_type_name(), expected_type);
+            let (outward, inward) = relation.outward_inward_keys(key, to);
+            assert_eq!(outward, expected_outward);
+            assert_eq!(inward, expected_inward);
+            if relation != LinkRelation::RelatesTo {
+                let to_is_outward = outward == to;
+                assert_eq!(
+                    LinkRelation::from_link(expected_type, to_is_outward),
+                    Some(relation)
+                );
+            }
+        }
+        assert_eq!(LinkRelation::from_link("Causes", true), None);
+    }
+}
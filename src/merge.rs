@@ -0,0 +1,499 @@
+//! Folding a duplicate issue into the issue it duplicates.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+use crate::adf::prepend_note;
+use crate::client::{JiraClient, Request};
+use crate::error::{ErrorKind, JiraError};
+use crate::link::LinkRelation;
+use crate::models::{Issue, Transition};
+
+/// Resolution set on the duplicate when its closing transition asks for one.
+pub const DUPLICATE_RESOLUTION: &str = "Duplicate";
+
+/// What moved onto the canonical issue and how the duplicate was closed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeReport {
+    pub duplicate: String,
+    pub into: String,
+    pub comments: usize,
+    /// File names of the copied attachments.
+    pub attachments: Vec<String>,
+    pub watchers: usize,
+    /// Labels the canonical issue did not have yet.
+    pub labels: Vec<String>,
+    /// Status the duplicate was moved to.
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<String>,
+}
+
+impl JiraClient {
+    /// Links `duplicate` to `canonical` as a duplicate, copies its comments
+    /// (with a note naming the original author), attachments, watchers and
+    /// labels over, then closes it with resolution "Duplicate". The closing
+    /// transition is looked up first, so nothing changes when there is none,
+    /// and an existing duplicate link between the two is taken as an earlier
+    /// merge and refused. The steps are not atomic: when one fails, the error
+    /// lists the ones already done.
+    pub async fn merge_issues(&self, duplicate: &str, canonical: &str) -> Result<MergeReport> {
+        if duplicate.eq_ignore_ascii_case(canonical) {
+            return Err(JiraError::new(
+                ErrorKind::Validation,
+                format!("Cannot merge {duplicate} into itself"),
+            )
+            .into());
+        }
+        let source: Issue = self
+            .execute_as(
+                Request::get(format!("issue/{duplicate}"))
+                    .query("fields", "labels,attachment,issuelinks")
+                    .action("get issue"),
+            )
+            .await?;
+        let merged = source.fields.issuelinks.iter().flatten().any(|link| {
+            link.link_type
+                .name
+                .eq_ignore_ascii_case(LinkRelation::Duplicates.link_type_name())
+                && link
+                    .target()
+                    .is_some_and(|(_, other)| other.key.eq_ignore_ascii_case(canonical))
+        });
+        if merged {
+            return Err(JiraError::new(
+                ErrorKind::Validation,
+                format!(
+                    "{duplicate} already has a duplicate link to {canonical}; it looks merged already"
+                ),
+            )
+            .into());
+        }
+        let target: Issue = self
+            .execute_as(
+                Request::get(format!("issue/{canonical}"))
+                    .query("fields", "labels")
+                    .action("get issue"),
+            )
+            .await?;
+        let closing = self.closing_transition(duplicate).await?;
+
+        let mut done = Vec::new();
+        self.merge_steps(duplicate, canonical, &source, &target, closing, &mut done)
+            .await
+            .map_err(|err| {
+                if done.is_empty() {
+                    return err;
+                }
+                err.context(format!(
+                    "Merging {duplicate} into {canonical} stopped after: {}",
+                    done.join("; ")
+                ))
+            })
+    }
+
+    /// The changing part of [`JiraClient::merge_issues`]; each step that goes
+    /// through is appended to `done`.
+    async fn merge_steps(
+        &self,
+        duplicate: &str,
+        canonical: &str,
+        source: &Issue,
+        target: &Issue,
+        (closing, fields): (Transition, Option<Map<String, Value>>),
+        done: &mut Vec<String>,
+    ) -> Result<MergeReport> {
+        self.link_issues(duplicate, canonical, LinkRelation::Duplicates)
+            .await?;
+        done.push(format!("linked {duplicate} as a duplicate of {canonical}"));
+
+        let comments = self.get_comments(duplicate).await?;
+        for comment in &comments {
+            let author = comment
+                .author
+                .as_ref()
+                .map(|a| a.display_name.as_str())
+                .unwrap_or("an unknown user");
+            let created = comment.created.as_deref().unwrap_or_default();
+            let note = format!(
+                "Comment by {author} on {}, copied from {duplicate}:",
+                created.get(..10).unwrap_or(created)
+            );
+            let body = prepend_note(&note, comment.body.as_ref().unwrap_or(&Value::Null));
+            self.execute(
+                Request::post(
+                    format!("issue/{canonical}/comment"),
+                    json!({ "body": body }),
+                )
+                .action("add comment"),
+            )
+            .await?;
+            done.push(format!("copied comment by {author}"));
+        }
+
+        let mut attachments = Vec::new();
+        let source_attachments = source.fields.extra.get("attachment");
+        for attachment in source_attachments
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let id = attachment["id"].as_str().unwrap_or_default();
+            let filename = attachment["filename"].as_str().unwrap_or(id);
+            let mime_type = attachment["mimeType"]
+                .as_str()
+                .unwrap_or("application/octet-stream");
+            let content = self
+                .download_attachment(id)
+                .await
+                .with_context(|| format!("Failed to download {filename}"))?;
+            self.add_attachment(canonical, filename, mime_type, content)
+                .await
+                .with_context(|| format!("Failed to attach {filename} to {canonical}"))?;
+            done.push(format!("attached {filename}"));
+            attachments.push(filename.to_string());
+        }
+
+        let watchers = self
+            .execute(Request::get(format!("issue/{duplicate}/watchers")).action("get watchers"))
+            .await?;
+        let watchers: Vec<&str> = watchers["watchers"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|w| w["accountId"].as_str())
+            .collect();
+        for account_id in &watchers {
+            self.execute(
+                Request::post(format!("issue/{canonical}/watchers"), json!(account_id))
+                    .idempotent()
+                    .action("add watcher"),
+            )
+            .await?;
+            done.push(format!("added watcher {account_id}"));
+        }
+
+        let mut labels = target.fields.labels.clone().unwrap_or_default();
+        let added: Vec<String> = source
+            .fields
+            .labels
+            .iter()
+            .flatten()
+            .filter(|label| !labels.contains(label))
+            .cloned()
+            .collect();
+        if !added.is_empty() {
+            labels.extend(added.iter().cloned());
+            let mut fields = Map::new();
+            fields.insert("labels".to_string(), json!(labels));
+            self.update_issue(canonical, fields).await?;
+            done.push(format!("added labels {}", added.join(", ")));
+        }
+
+        let resolution = fields.is_some().then(|| DUPLICATE_RESOLUTION.to_string());
+        self.run_transition(duplicate, &closing, fields).await?;
+        Ok(MergeReport {
+            duplicate: duplicate.to_string(),
+            into: canonical.to_string(),
+            comments: comments.len(),
+            attachments,
+            watchers: watchers.len(),
+            labels: added,
+            status: closing.to.map(|to| to.name).unwrap_or(closing.name),
+            resolution,
+        })
+    }
+
+    /// A transition into a done status, preferring one whose screen sets the
+    /// resolution, with the fields that set it to "Duplicate".
+    async fn closing_transition(
+        &self,
+        key: &str,
+    ) -> Result<(Transition, Option<Map<String, Value>>)> {
+        let payload = self
+            .execute(
+                Request::get(format!("issue/{key}/transitions"))
+                    .query("expand", "transitions.fields")
+                    .action("get transitions"),
+            )
+            .await?;
+        let transitions: Vec<Transition> = serde_json::from_value(payload["transitions"].clone())
+            .context("Failed to parse get transitions response")?;
+        let mut closing: Vec<Transition> = transitions
+            .into_iter()
+            .filter(|t| {
+                t.to.as_ref()
+                    .and_then(|to| to.status_category.as_ref())
+                    .is_some_and(|category| category.key == "done")
+            })
+            .collect();
+        closing.sort_by_key(|t| resolution_field(t).is_none());
+        let transition = closing.into_iter().next().ok_or_else(|| {
+            JiraError::new(
+                ErrorKind::Validation,
+                format!("No transition closes {key} from its current status"),
+            )
+        })?;
+
+        let Some(resolution) = resolution_field(&transition) else {
+            return Ok((transition, None));
+        };
+        let allowed: Vec<&str> = resolution["allowedValues"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|value| value["name"].as_str())
+            .collect();
+        if !allowed.is_empty()
+            && !allowed
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(DUPLICATE_RESOLUTION))
+        {
+            return Err(JiraError::new(
+                ErrorKind::Validation,
+                format!(
+                    "Transition '{}' does not offer resolution '{DUPLICATE_RESOLUTION}' (only {})",
+                    transition.name,
+                    allowed.join(", ")
+                ),
+            )
+            .into());
+        }
+        let mut fields = Map::new();
+        fields.insert(
+            "resolution".to_string(),
+            json!({ "name": DUPLICATE_RESOLUTION }),
+        );
+        Ok((transition, Some(fields)))
+    }
+}
+
+/// The resolution field on a transition's screen, from `expand=transitions.fields`.
+fn resolution_field(transition: &Transition) -> Option<&Value> {
+    transition.extra.get("fields")?.get("resolution")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_client;
+    use httpmock::prelude::*;
+
+    #[tokio::test]
+    async fn merge_copies_everything_then_closes_as_duplicate() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/api/3/issue/ACME-2")
+                .query_param("fields", "labels,attachment,issuelinks");
+            then.status(200).json_body(json!({
+                "key": "ACME-2",
+                "fields": {
+                    "labels": ["ui", "login"],
+                    "attachment": [{ "id": "77", "filename": "trace.txt", "mimeType": "text/plain" }]
+                }
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/api/3/issue/ACME-1")
+                .query_param("fields", "labels");
+            then.status(200)
+                .json_body(json!({ "key": "ACME-1", "fields": { "labels": ["login"] } }));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/api/3/issue/ACME-2/transitions")
+                .query_param("expand", "transitions.fields");
+            then.status(200).json_body(json!({ "transitions": [
+                { "id": "11", "name": "Start", "to": { "name": "In Progress",
+                    "statusCategory": { "key": "indeterminate" } } },
+                { "id": "31", "name": "Done", "to": { "name": "Done",
+                    "statusCategory": { "key": "done" } } },
+                { "id": "41", "name": "Close", "to": { "name": "Closed",
+                    "statusCategory": { "key": "done" } },
+                  "fields": { "resolution": { "allowedValues": [
+                      { "name": "Fixed" }, { "name": "Duplicate" }
+                  ] } } }
+            ] }));
+        });
+        let link = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/issueLink")
+                .json_body(json!({
+                    "type": { "name": "Duplicate" },
+                    "outwardIssue": { "key": "ACME-1" },
+                    "inwardIssue": { "key": "ACME-2" }
+                }));
+            then.status(201);
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-2/comment");
+            then.status(200).json_body(json!({ "comments": [{
+                "id": "5",
+                "author": { "accountId": "a1", "displayName": "Ada" },
+                "created": "2026-10-01T09:30:00.000+0000",
+                "body": { "type": "doc", "version": 1, "content": [
+                    { "type": "paragraph", "content": [{ "type": "text", "text": "Same crash" }] }
+                ] }
+            }] }));
+        });
+        let comment = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/issue/ACME-1/comment")
+                .json_body(json!({ "body": { "type": "doc", "version": 1, "content": [
+                    { "type": "paragraph", "content": [{ "type": "text",
+                        "text": "Comment by Ada on 2026-10-01, copied from ACME-2:",
+                        "marks": [{ "type": "em" }] }] },
+                    { "type": "paragraph", "content": [{ "type": "text", "text": "Same crash" }] }
+                ] } }));
+            then.status(201).json_body(json!({ "id": "6" }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/attachment/content/77");
+            then.status(200).body("stack trace");
+        });
+        let upload = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/issue/ACME-1/attachments")
+                .header("x-atlassian-token", "no-check")
+                .body_contains("filename=\"trace.txt\"")
+                .body_contains("stack trace");
+            then.status(200).json_body(json!([{ "id": "78" }]));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-2/watchers");
+            then.status(200)
+                .json_body(json!({ "watchers": [{ "accountId": "a1" }, { "accountId": "b2" }] }));
+        });
+        let watch = server.mock(|when, then| {
+            when.method(POST).path("/rest/api/3/issue/ACME-1/watchers");
+            then.status(204);
+        });
+        let labels = server.mock(|when, then| {
+            when.method(PUT)
+                .path("/rest/api/3/issue/ACME-1")
+                .json_body(json!({ "fields": { "labels": ["login", "ui"] } }));
+            then.status(204);
+        });
+        let close = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/issue/ACME-2/transitions")
+                .json_body(json!({
+                    "transition": { "id": "41" },
+                    "fields": { "resolution": { "name": "Duplicate" } }
+                }));
+            then.status(204);
+        });
+
+        let report = test_client(&server)
+            .merge_issues("ACME-2", "ACME-1")
+            .await
+            .unwrap();
+
+        link.assert();
+        comment.assert();
+        upload.assert();
+        watch.assert_hits(2);
+        labels.assert();
+        close.assert();
+        assert_eq!(report.attachments, ["trace.txt"]);
+        assert_eq!(report.labels, ["ui"]);
+        assert_eq!(report.status, "Closed");
+        assert_eq!(report.resolution.as_deref(), Some("Duplicate"));
+    }
+
+    #[tokio::test]
+    async fn merge_refuses_a_duplicate_already_linked() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-2");
+            then.status(200).json_body(json!({ "key": "ACME-2", "fields": { "issuelinks": [{
+                "type": { "name": "Duplicate", "inward": "is duplicated by", "outward": "duplicates" },
+                "outwardIssue": { "key": "ACME-1" }
+            }] } }));
+        });
+        let writes = server.mock(|when, then| {
+            when.method(POST);
+            then.status(201);
+        });
+
+        let err = test_client(&server)
+            .merge_issues("ACME-2", "ACME-1")
+            .await
+            .unwrap_err();
+
+        writes.assert_hits(0);
+        assert_eq!(
+            err.to_string(),
+            "ACME-2 already has a duplicate link to ACME-1; it looks merged already"
+        );
+    }
+
+    #[tokio::test]
+    async fn merge_failure_lists_the_steps_already_done() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-2");
+            then.status(200)
+                .json_body(json!({ "key": "ACME-2", "fields": {
+                "attachment": [{ "id": "77", "filename": "trace.txt" }]
+            } }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-1");
+            then.status(200)
+                .json_body(json!({ "key": "ACME-1", "fields": {} }));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/api/3/issue/ACME-2/transitions");
+            then.status(200).json_body(json!({ "transitions": [
+                { "id": "31", "name": "Done", "to": { "name": "Done",
+                    "statusCategory": { "key": "done" } } }
+            ] }));
+        });
+        server.mock(|when, then| {
+            when.method(POST).path("/rest/api/3/issueLink");
+            then.status(201);
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-2/comment");
+            then.status(200).json_body(json!({ "comments": [
+                { "id": "5", "author": { "displayName": "Ada" }, "body": null }
+            ] }));
+        });
+        server.mock(|when, then| {
+            when.method(POST).path("/rest/api/3/issue/ACME-1/comment");
+            then.status(201).json_body(json!({ "id": "6" }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/attachment/content/77");
+            then.status(404)
+                .json_body(json!({ "errorMessages": ["Attachment is gone"] }));
+        });
+        let close = server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/issue/ACME-2/transitions");
+            then.status(204);
+        });
+
+        let err = test_client(&server)
+            .merge_issues("ACME-2", "ACME-1")
+            .await
+            .unwrap_err();
+
+        close.assert_hits(0);
+        assert_eq!(
+            err.to_string(),
+            "Merging ACME-2 into ACME-1 stopped after: linked ACME-2 as a duplicate of ACME-1; copied comment by Ada"
+        );
+        assert!(format!("{err:#}").contains("Failed to download trace.txt"));
+        assert_eq!(
+            err.downcast_ref::<JiraError>().map(|e| e.kind),
+            Some(ErrorKind::NotFound)
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! Create metadata: which issue types a project offers and which fields each
+//! of them accepts on create.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::client::{JiraClient, Request};
+use crate::error::{ErrorKind, JiraError};
+use crate::models::IssueType;
+
+/// Entries per create metadata page.
+const META_PAGE_SIZE: u32 = 50;
+
+/// A field that can be set when creating an issue of some type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateField {
+    pub id: String,
+    pub required: bool,
+    /// The `schema.custom` type of custom fields, e.g. `com.pyxis.greenhopper.jira:gh-sprint`.
+    pub custom: Option<String>,
+}
+
+impl JiraClient {
+    /// Issue types that can be created in `project`.
+    pub async fn issue_types(&self, project: &str) -> Result<Vec<IssueType>> {
+        let values = self
+            .create_meta(
+                format!("issue/createmeta/{project}/issuetypes"),
+                "issueTypes",
+            )
+            .await?;
+        serde_json::from_value(Value::Array(values)).context("Failed to parse issue types")
+    }
+
+    /// The issue type of `project` with this name (case-insensitive).
+    pub async fn issue_type(&self, project: &str, name: &str) -> Result<IssueType> {
+        let types = self.issue_types(project).await?;
+        types
+            .into_iter()
+            .find(|t| t.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                JiraError::new(
+                    ErrorKind::Validation,
+                    format!("Issue type '{name}' is not available in project {project}"),
+                )
+                .into()
+            })
+    }
+
+    /// Fields an issue of type `issue_type_id` accepts on create in `project`.
+    pub async fn create_fields(
+        &self,
+        project: &str,
+        issue_type_id: &str,
+    ) -> Result<Vec<CreateField>> {
+        let values = self
+            .create_meta(
+                format!("issue/createmeta/{project}/issuetypes/{issue_type_id}"),
+                "fields",
+            )
+            .await?;
+        Ok(values
+            .iter()
+            .filter_map(|field| {
+                Some(CreateField {
+                    id: field.get("fieldId")?.as_str()?.to_string(),
+                    required: field["required"].as_bool().unwrap_or(false),
+                    custom: field["schema"]["custom"].as_str().map(str::to_string),
+                })
+            })
+            .collect())
+    }
+
+    /// Collects every page of a create metadata list. Jira names the list
+    /// `list_key`, or `results` on newer sites.
+    async fn create_meta(&self, path: String, list_key: &str) -> Result<Vec<Value>> {
+        let mut values = Vec::new();
+        loop {
+            let page = self
+                .execute(
+                    Request::get(path.as_str())
+                        .query("startAt", values.len())
+                        .query("maxResults", META_PAGE_SIZE)
+                        .action("create metadata"),
+                )
+                .await?;
+            let items = page
+                .get(list_key)
+                .or_else(|| page.get("results"))
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+            let fetched = items.len();
+            values.extend(items);
+            let total = page["total"].as_u64().unwrap_or(0) as usize;
+            if fetched == 0 || values.len() >= total {
+                return Ok(values);
+            }
+        }
+    }
+}
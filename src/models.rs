@@ -0,0 +1,347 @@
+//! Typed views of the Jira resources jico works with.
+//!
+//! Only the commonly used attributes are modeled; everything else Jira sends
+//! (custom fields, expansions, `renderedFields`, ...) is kept in the `extra`
+//! maps so it survives a round trip through these types.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Issue {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub key: String,
+    #[serde(rename = "self", default, skip_serializing_if = "Option::is_none")]
+    pub self_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Fields::is_empty")]
+    pub fields: Fields,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Issue {
+    pub fn status_name(&self) -> Option<&str> {
+        self.fields.status.as_ref().map(|s| s.name.as_str())
+    }
+
+    /// `new`, `indeterminate` or `done`, when Jira included the status category.
+    pub fn status_category(&self) -> Option<&str> {
+        self.fields
+            .status
+            .as_ref()
+            .and_then(|s| s.status_category.as_ref())
+            .map(|c| c.key.as_str())
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.status_category() == Some("done")
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Fields {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuetype: Option<IssueType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<NamedRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<NamedRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<Project>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<User>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reporter: Option<User>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Box<Issue>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtasks: Option<Vec<Issue>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuelinks: Option<Vec<IssueLink>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolutiondate: Option<String>,
+    /// Custom fields and any system field not modeled above.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Fields {
+    pub fn is_empty(&self) -> bool {
+        *self == Fields::default()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Status {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_category: Option<StatusCategory>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatusCategory {
+    #[serde(default)]
+    pub key: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueType {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub subtask: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hierarchy_level: Option<i32>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Priority, resolution and similar `{id, name}` references.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NamedRef {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Project {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub key: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    #[serde(default)]
+    pub account_id: String,
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueLink {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "type")]
+    pub link_type: IssueLinkType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inward_issue: Option<Issue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outward_issue: Option<Issue>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl IssueLink {
+    /// The issue on the other end and the verb that reads from the issue
+    /// holding this link, e.g. `blocks` or `is blocked by`.
+    pub fn target(&self) -> Option<(&str, &Issue)> {
+        match (&self.outward_issue, &self.inward_issue) {
+            (Some(other), _) => Some((self.link_type.outward.as_str(), other)),
+            (None, Some(other)) => Some((self.link_type.inward.as_str(), other)),
+            (None, None) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IssueLinkType {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub inward: String,
+    #[serde(default)]
+    pub outward: String,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Transition {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Status>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Comment {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<User>,
+    /// Atlassian Document Format body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Time logged on an issue.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Worklog {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<User>,
+    /// When the work started, e.g. `2026-10-01T09:00:00.000+0000`.
+    #[serde(default)]
+    pub started: String,
+    #[serde(default)]
+    pub time_spent_seconds: i64,
+    /// Atlassian Document Format comment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<Value>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// One page of `/search/jql` results.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResults {
+    #[serde(default)]
+    pub issues: Vec<Issue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_last: Option<bool>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A Jira Software board (agile API).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Board {
+    pub id: u64,
+    #[serde(default)]
+    pub name: String,
+    /// `scrum`, `kanban` or `simple`.
+    #[serde(rename = "type", default)]
+    pub board_type: String,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A sprint on a scrum board (agile API).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sprint {
+    pub id: u64,
+    #[serde(default)]
+    pub name: String,
+    /// `future`, `active` or `closed`.
+    #[serde(default)]
+    pub state: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub complete_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin_board_id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goal: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn issue_round_trips_unknown_fields() {
+        let raw = json!({
+            "id": "10001",
+            "key": "ACME-1",
+            "self": "https://acme.atlassian.net/rest/api/3/issue/10001",
+            "expand": "renderedFields",
+            "fields": {
+                "summary": "Fix login",
+                "status": {
+                    "id": "3",
+                    "name": "In Progress",
+                    "statusCategory": { "key": "indeterminate", "name": "In Progress" }
+                },
+                "assignee": { "accountId": "abc", "displayName": "Ada" },
+                "labels": [],
+                "customfield_10016": 5
+            }
+        });
+
+        let issue: Issue = serde_json::from_value(raw.clone()).unwrap();
+        assert_eq!(issue.key, "ACME-1");
+        assert_eq!(issue.fields.summary.as_deref(), Some("Fix login"));
+        assert_eq!(issue.status_category(), Some("indeterminate"));
+        assert_eq!(issue.fields.assignee.as_ref().unwrap().account_id, "abc");
+        assert_eq!(issue.fields.extra["customfield_10016"], 5);
+        assert_eq!(serde_json::to_value(&issue).unwrap(), raw);
+    }
+
+    #[test]
+    fn issue_with_only_a_key_serializes_without_fields() {
+        let issue: Issue = serde_json::from_value(json!({ "id": "1", "key": "ACME-2" })).unwrap();
+        assert_eq!(
+            serde_json::to_value(&issue).unwrap(),
+            json!({ "id": "1", "key": "ACME-2" })
+        );
+    }
+}
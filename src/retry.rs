@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use reqwest::{StatusCode, header};
+
+/// How often and how patiently requests are retried on 429/502/503/504.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+
+    pub(crate) fn should_retry(status: StatusCode) -> bool {
+        matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT
+        )
+    }
+
+    /// Exponential backoff with "equal jitter": half of the delay is fixed, the other half random.
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let exp = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+        let half = exp / 2;
+        half + half.mul_f64(fastrand::f64())
+    }
+
+    pub(crate) fn delay(&self, attempt: u32, headers: &header::HeaderMap) -> Duration {
+        retry_after(headers)
+            .map(|delay| delay.min(self.max_delay))
+            .unwrap_or_else(|| self.backoff(attempt))
+    }
+}
+
+/// Parses a `Retry-After` header given in seconds (the form Jira Cloud sends).
+pub(crate) fn retry_after(headers: &header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_policy_backoff_grows_and_respects_retry_after() {
+        let policy = RetryPolicy::new(5);
+        let first = policy.backoff(1);
+        assert!(first >= Duration::from_millis(250) && first <= Duration::from_millis(500));
+        let third = policy.backoff(3);
+        assert!(third >= Duration::from_millis(1000) && third <= Duration::from_millis(2000));
+        assert!(policy.backoff(30) <= policy.max_delay);
+
+        let mut headers = header::HeaderMap::new();
+        headers.insert(header::RETRY_AFTER, header::HeaderValue::from_static("7"));
+        assert_eq!(policy.delay(1, &headers), Duration::from_secs(7));
+        headers.insert(
+            header::RETRY_AFTER,
+            header::HeaderValue::from_static("3600"),
+        );
+        assert_eq!(policy.delay(1, &headers), policy.max_delay);
+    }
+}
@@ -0,0 +1,82 @@
+use std::env;
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+
+use crate::error::{ErrorKind, JiraError};
+use crate::journal::default_journal_path;
+
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 4;
+
+/// Connection settings and defaults for a Jira Cloud site.
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub base_url: String,
+    pub email: String,
+    pub api_token: String,
+    pub project_key: Option<String>,
+    pub default_jql: Option<String>,
+    pub max_attempts: u32,
+    /// Custom field holding story points; discovered by name when unset.
+    pub story_points_field: Option<String>,
+    /// File recording changes for `undo`; `None` turns the journal off.
+    pub journal: Option<PathBuf>,
+}
+
+impl Settings {
+    pub fn new(base_url: &str, email: &str, api_token: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            email: email.to_string(),
+            api_token: api_token.to_string(),
+            project_key: None,
+            default_jql: None,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            story_points_field: None,
+            journal: None,
+        }
+    }
+
+    /// Reads `JIRA_*` variables from the process environment.
+    pub fn load() -> Result<Self> {
+        let base_url = required_env("JIRA_BASE_URL")?;
+        let email = required_env("JIRA_EMAIL")?;
+        let api_token = required_env("JIRA_API_TOKEN")?;
+        let max_attempts = match env::var("JIRA_MAX_ATTEMPTS") {
+            Ok(value) => value
+                .parse::<u32>()
+                .ok()
+                .filter(|attempts| *attempts > 0)
+                .ok_or_else(|| {
+                    JiraError::new(
+                        ErrorKind::Config,
+                        "JIRA_MAX_ATTEMPTS must be a positive integer",
+                    )
+                })?,
+            Err(_) => DEFAULT_MAX_ATTEMPTS,
+        };
+
+        Ok(Self {
+            project_key: env::var("JIRA_PROJECT_KEY").ok(),
+            default_jql: env::var("JIRA_DEFAULT_JQL").ok(),
+            max_attempts,
+            story_points_field: env::var("JIRA_STORY_POINTS_FIELD").ok(),
+            journal: match env::var("JIRA_JOURNAL") {
+                Ok(value) if value.is_empty() || value.eq_ignore_ascii_case("off") => None,
+                Ok(value) if value.eq_ignore_ascii_case("on") => default_journal_path(),
+                Ok(value) => Some(PathBuf::from(value)),
+                Err(_) => default_journal_path(),
+            },
+            ..Self::new(&base_url, &email, &api_token)
+        })
+    }
+}
+
+fn required_env(key: &str) -> Result<String> {
+    env::var(key).map_err(|_| {
+        anyhow!(JiraError::new(
+            ErrorKind::Config,
+            format!("Missing {key} (set in environment or .env)"),
+        ))
+    })
+}
@@ -0,0 +1,316 @@
+//! A user's recent activity and open work, for a daily standup.
+
+use anyhow::Result;
+use chrono::{DateTime, Days, Duration, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::Serialize;
+
+use crate::changelog::parse_jira_time;
+use crate::client::JiraClient;
+use crate::dependency::open_blockers;
+use crate::error::{ErrorKind, JiraError};
+use crate::models::{Issue, User};
+
+/// Fields fetched for the user's issues.
+const STANDUP_FIELDS: [&str; 3] = ["summary", "status", "issuelinks"];
+/// Issues whose activity is fetched at once.
+const ACTIVITY_CONCURRENCY: usize = 8;
+
+/// What the user did on one issue since the cut-off.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Activity {
+    pub key: String,
+    pub summary: String,
+    pub status: String,
+    /// Status changes made by the user, e.g. `To Do → In Progress`.
+    pub transitions: Vec<String>,
+    /// Comments the user added.
+    pub comments: usize,
+    /// Time the user logged, in seconds.
+    pub logged_seconds: i64,
+}
+
+/// An issue assigned to the user that is still open.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenWork {
+    pub key: String,
+    pub summary: String,
+    pub status: String,
+    /// Unresolved issues blocking this one.
+    pub blocked_by: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Standup {
+    pub user: User,
+    pub since: String,
+    pub activity: Vec<Activity>,
+    /// In progress and not blocked.
+    pub in_progress: Vec<OpenWork>,
+    /// Open issues with an unresolved blocker or in a status named "Blocked".
+    pub blocked: Vec<OpenWork>,
+}
+
+/// Reads `--since`: `today`, `yesterday` (midnight starting that day),
+/// `YYYY-MM-DD` (midnight), an RFC 3339 timestamp, or an age such as
+/// `36h`, `2d` or `90m`, all relative to `now` and in its timezone.
+pub fn parse_since<Tz: TimeZone>(value: &str, now: &DateTime<Tz>) -> Result<DateTime<FixedOffset>> {
+    let midnight = |date: NaiveDate| {
+        now.timezone()
+            .from_local_datetime(&date.and_time(NaiveTime::MIN))
+            .earliest()
+            .map(|at| at.fixed_offset())
+    };
+    let today = now.date_naive();
+    let value = value.trim();
+    let age = |unit: char| {
+        let count: i64 = value.strip_suffix(unit)?.parse().ok()?;
+        let age = match unit {
+            'd' => Duration::days(count),
+            'h' => Duration::hours(count),
+            _ => Duration::minutes(count),
+        };
+        Some(now.fixed_offset() - age)
+    };
+    let parsed = match value.to_ascii_lowercase().as_str() {
+        "today" => midnight(today),
+        "yesterday" => today.checked_sub_days(Days::new(1)).and_then(midnight),
+        _ => age('d')
+            .or_else(|| age('h'))
+            .or_else(|| age('m'))
+            .or_else(|| DateTime::parse_from_rfc3339(value).ok())
+            .or_else(|| {
+                NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .ok()
+                    .and_then(midnight)
+            }),
+    };
+    parsed.ok_or_else(|| {
+        JiraError::new(
+            ErrorKind::Validation,
+            format!(
+                "Cannot read --since '{value}'; use today, yesterday, YYYY-MM-DD, an RFC 3339 time or an age like 36h"
+            ),
+        )
+        .into()
+    })
+}
+
+fn open_work(issue: &Issue) -> OpenWork {
+    OpenWork {
+        key: issue.key.clone(),
+        summary: issue.fields.summary.clone().unwrap_or_default(),
+        status: issue.status_name().unwrap_or_default().to_string(),
+        blocked_by: open_blockers(issue).map(str::to_string).collect(),
+    }
+}
+
+impl JiraClient {
+    /// Issues `user` (see [`JiraClient::find_user`]) transitioned, commented
+    /// on or logged time against since `since`, plus their open work.
+    pub async fn standup(&self, user: &str, since: DateTime<FixedOffset>) -> Result<Standup> {
+        let user = self.find_user(user).await?;
+        let id = &user.account_id;
+        let now = Utc::now();
+        // Relative JQL dates sidestep the user's profile timezone.
+        let minutes = (now.fixed_offset() - since).num_minutes().max(0) + 1;
+        let jql = format!(
+            "updated >= -{minutes}m AND (issue in updatedBy(\"{id}\", \"-{minutes}m\") OR worklogAuthor = \"{id}\") ORDER BY updated DESC"
+        );
+        let touched = self.search_all(&jql, &STANDUP_FIELDS).await?;
+        let activity: Vec<Option<Activity>> = stream::iter(&touched)
+            .map(|issue| self.activity(issue, id, since, now))
+            .buffered(ACTIVITY_CONCURRENCY)
+            .try_collect()
+            .await?;
+
+        let jql = format!(
+            "assignee = \"{id}\" AND statusCategory != Done ORDER BY priority DESC, key ASC"
+        );
+        let open = self.search_all(&jql, &STANDUP_FIELDS).await?;
+        let mut in_progress = Vec::new();
+        let mut blocked = Vec::new();
+        for issue in &open {
+            let work = open_work(issue);
+            if !work.blocked_by.is_empty() || work.status.eq_ignore_ascii_case("Blocked") {
+                blocked.push(work);
+            } else if issue.status_category() == Some("indeterminate") {
+                in_progress.push(work);
+            }
+        }
+        Ok(Standup {
+            since: since.to_rfc3339(),
+            user,
+            activity: activity.into_iter().flatten().collect(),
+            in_progress,
+            blocked,
+        })
+    }
+
+    /// What `account_id` did on `issue` since `since`, if anything.
+    async fn activity(
+        &self,
+        issue: &Issue,
+        account_id: &str,
+        since: DateTime<FixedOffset>,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Activity>> {
+        let by_user = |author: Option<&User>| author.is_some_and(|a| a.account_id == account_id);
+        let recent = |at: Option<&str>| at.and_then(parse_jira_time).is_some_and(|at| at >= since);
+
+        let changes = self.changelog(&issue.key).await?;
+        let transitions: Vec<String> = changes
+            .iter()
+            .filter(|set| by_user(set.author.as_ref()) && recent(Some(&set.created)))
+            .flat_map(|set| &set.items)
+            .filter(|item| item.is_field("status"))
+            .map(|item| {
+                format!(
+                    "{} → {}",
+                    item.from_text.as_deref().unwrap_or("?"),
+                    item.to_text.as_deref().unwrap_or("?")
+                )
+            })
+            .collect();
+        let comments = self
+            .get_comments(&issue.key)
+            .await?
+            .iter()
+            .filter(|c| by_user(c.author.as_ref()) && recent(c.created.as_deref()))
+            .count();
+        let logged_seconds = self
+            .worklogs(&issue.key, since.with_timezone(&Utc), now)
+            .await?
+            .iter()
+            .filter(|w| by_user(w.author.as_ref()) && recent(Some(&w.started)))
+            .map(|w| w.time_spent_seconds)
+            .sum();
+
+        if transitions.is_empty() && comments == 0 && logged_seconds == 0 {
+            return Ok(None);
+        }
+        Ok(Some(Activity {
+            key: issue.key.clone(),
+            summary: issue.fields.summary.clone().unwrap_or_default(),
+            status: issue.status_name().unwrap_or_default().to_string(),
+            transitions,
+            comments,
+            logged_seconds,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_client;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    #[test]
+    fn since_accepts_days_ages_and_dates() {
+        let now = DateTime::parse_from_rfc3339("2026-10-18T09:30:00+02:00").unwrap();
+        let since = |value: &str| parse_since(value, &now).unwrap().to_rfc3339();
+        assert_eq!(since("yesterday"), "2026-10-17T00:00:00+02:00");
+        assert_eq!(since("today"), "2026-10-18T00:00:00+02:00");
+        assert_eq!(since("36h"), "2026-10-16T21:30:00+02:00");
+        assert_eq!(since("2026-10-15"), "2026-10-15T00:00:00+02:00");
+        assert!(parse_since("last week", &now).is_err());
+    }
+
+    #[tokio::test]
+    async fn standup_collects_the_users_own_activity_and_open_work() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/myself");
+            then.status(200)
+                .json_body(json!({ "accountId": "a1", "displayName": "Ada" }));
+        });
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/search/jql")
+                .body_contains("updatedBy");
+            then.status(200).json_body(json!({ "isLast": true, "issues": [
+                { "key": "ACME-1", "fields": { "summary": "Login", "status": { "name": "Done" } } },
+                { "key": "ACME-2", "fields": { "summary": "Logout", "status": { "name": "To Do" } } }
+            ] }));
+        });
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/search/jql")
+                .body_contains("statusCategory != Done");
+            then.status(200).json_body(json!({ "isLast": true, "issues": [
+                { "key": "ACME-3", "fields": { "summary": "Search",
+                    "status": { "name": "In Progress", "statusCategory": { "key": "indeterminate" } } } },
+                { "key": "ACME-4", "fields": { "summary": "Payments",
+                    "status": { "name": "In Progress", "statusCategory": { "key": "indeterminate" } },
+                    "issuelinks": [{ "type": { "name": "Blocks" },
+                        "inwardIssue": { "key": "ACME-9",
+                            "fields": { "status": { "statusCategory": { "key": "new" } } } } }] } }
+            ] }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-1/changelog");
+            then.status(200).json_body(json!({ "isLast": true, "values": [
+                { "id": "1", "author": { "accountId": "a1" }, "created": "2026-10-17T10:00:00.000+0000",
+                  "items": [{ "field": "status", "fromString": "In Progress", "toString": "Done" }] },
+                { "id": "2", "author": { "accountId": "a1" }, "created": "2026-10-10T10:00:00.000+0000",
+                  "items": [{ "field": "status", "fromString": "To Do", "toString": "In Progress" }] }
+            ] }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-1/comment");
+            then.status(200).json_body(json!({ "comments": [
+                { "id": "5", "author": { "accountId": "a1" }, "created": "2026-10-17T11:00:00.000+0000" },
+                { "id": "6", "author": { "accountId": "b2" }, "created": "2026-10-17T12:00:00.000+0000" }
+            ] }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-1/worklog");
+            then.status(200).json_body(json!({ "total": 1, "worklogs": [
+                { "author": { "accountId": "a1" }, "started": "2026-10-17T09:00:00.000+0000",
+                  "timeSpentSeconds": 5400 }
+            ] }));
+        });
+        // Someone else's change brought ACME-2 into the search.
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-2/changelog");
+            then.status(200).json_body(json!({ "isLast": true, "values": [
+                { "id": "3", "author": { "accountId": "b2" }, "created": "2026-10-17T10:00:00.000+0000",
+                  "items": [{ "field": "status", "fromString": "Done", "toString": "To Do" }] }
+            ] }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-2/comment");
+            then.status(200).json_body(json!({ "comments": [] }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-2/worklog");
+            then.status(200)
+                .json_body(json!({ "total": 0, "worklogs": [] }));
+        });
+        let client = test_client(&server);
+        let since = DateTime::parse_from_rfc3339("2026-10-17T00:00:00+00:00").unwrap();
+
+        let standup = client.standup("me", since).await.unwrap();
+
+        assert_eq!(
+            standup.activity,
+            [Activity {
+                key: "ACME-1".to_string(),
+                summary: "Login".to_string(),
+                status: "Done".to_string(),
+                transitions: vec!["In Progress → Done".to_string()],
+                comments: 1,
+                logged_seconds: 5400,
+            }]
+        );
+        let keys = |work: &[OpenWork]| work.iter().map(|w| w.key.clone()).collect::<Vec<_>>();
+        assert_eq!(keys(&standup.in_progress), ["ACME-3"]);
+        assert_eq!(keys(&standup.blocked), ["ACME-4"]);
+        assert_eq!(standup.blocked[0].blocked_by, ["ACME-9"]);
+    }
+}
@@ -0,0 +1,373 @@
+//! Booked hours per user, collected from worklogs.
+//!
+//! A worklog counts on the day it started in its author's timezone: the one
+//! given explicitly, else the author's Jira profile timezone, else the local one.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Days, FixedOffset, Local, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::Serialize;
+
+use crate::changelog::parse_jira_time;
+use crate::client::{JiraClient, Request};
+use crate::error::{ErrorKind, JiraError};
+use crate::hierarchy::hierarchy_level;
+use crate::models::{Issue, User, Worklog};
+
+/// Fields needed to label issues and find their epics.
+const TIMESHEET_FIELDS: [&str; 3] = ["summary", "parent", "issuetype"];
+/// Worklogs per page of an issue's worklog list.
+const WORKLOG_PAGE_SIZE: u32 = 1000;
+/// Issues whose worklogs are fetched at once.
+const WORKLOG_CONCURRENCY: usize = 8;
+/// Group label for time on issues outside any epic.
+pub const NO_EPIC: &str = "(no epic)";
+
+/// What the rows of a timesheet stand for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum GroupBy {
+    /// Every day of the range
+    #[default]
+    Day,
+    /// Each issue with time logged
+    Issue,
+    /// The epic above each issue
+    Epic,
+}
+
+/// Hours per user for one day, issue or epic, in the order of [`Timesheet::users`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimesheetRow {
+    pub group: String,
+    pub hours: Vec<f64>,
+    pub total: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Timesheet {
+    /// Display names.
+    pub users: Vec<String>,
+    pub rows: Vec<TimesheetRow>,
+    /// Hours per user over the whole range.
+    pub totals: Vec<f64>,
+    pub total: f64,
+}
+
+/// The timezone days are counted in.
+enum Zone {
+    Named(Tz),
+    Local,
+}
+
+impl Zone {
+    fn date_of(&self, at: DateTime<FixedOffset>) -> NaiveDate {
+        match self {
+            Zone::Named(tz) => at.with_timezone(tz).date_naive(),
+            Zone::Local => at.with_timezone(&Local).date_naive(),
+        }
+    }
+}
+
+/// Parses an IANA timezone name such as `Europe/Berlin`.
+pub fn parse_timezone(name: &str) -> Result<Tz> {
+    name.parse().map_err(|_| {
+        JiraError::new(
+            ErrorKind::Validation,
+            format!("Unknown timezone '{name}'; use an IANA name such as Europe/Berlin"),
+        )
+        .into()
+    })
+}
+
+fn hours(seconds: i64) -> f64 {
+    (seconds as f64 / 36.0).round() / 100.0
+}
+
+/// `ACME-1 Summary`
+fn label(issue: &Issue) -> String {
+    match &issue.fields.summary {
+        Some(summary) => format!("{} {summary}", issue.key),
+        None => issue.key.clone(),
+    }
+}
+
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(NaiveTime::MIN).and_utc()
+}
+
+impl JiraClient {
+    /// Worklogs of `key` that started between `after` and `before`.
+    pub async fn worklogs(
+        &self,
+        key: &str,
+        after: DateTime<Utc>,
+        before: DateTime<Utc>,
+    ) -> Result<Vec<Worklog>> {
+        let mut worklogs: Vec<Worklog> = Vec::new();
+        loop {
+            let page = self
+                .execute(
+                    Request::get(format!("issue/{key}/worklog"))
+                        .query("startAt", worklogs.len())
+                        .query("maxResults", WORKLOG_PAGE_SIZE)
+                        .query("startedAfter", after.timestamp_millis())
+                        .query("startedBefore", before.timestamp_millis())
+                        .action("get worklogs"),
+                )
+                .await?;
+            let values: Vec<Worklog> =
+                serde_json::from_value(page.get("worklogs").cloned().unwrap_or_default())
+                    .context("Failed to parse get worklogs response")?;
+            let fetched = values.len();
+            worklogs.extend(values);
+            let total = page["total"].as_u64().unwrap_or(0) as usize;
+            if fetched == 0 || worklogs.len() >= total {
+                return Ok(worklogs);
+            }
+        }
+    }
+
+    /// Hours `users` (see [`JiraClient::find_user`]) logged from `from` to
+    /// `to` inclusive, grouped by `group_by`. With `timezone`, every author's
+    /// days are counted in it instead of in their own.
+    pub async fn timesheet(
+        &self,
+        users: &[String],
+        from: NaiveDate,
+        to: NaiveDate,
+        group_by: GroupBy,
+        timezone: Option<Tz>,
+    ) -> Result<Timesheet> {
+        if from > to {
+            return Err(JiraError::new(
+                ErrorKind::Validation,
+                format!("--from {from} is after --to {to}"),
+            )
+            .into());
+        }
+        let mut authors: Vec<User> = Vec::new();
+        for query in users {
+            authors.push(self.find_user(query).await?);
+        }
+        let zones: Vec<Zone> = authors
+            .iter()
+            .map(|author| {
+                timezone
+                    .or_else(|| author.time_zone.as_deref()?.parse().ok())
+                    .map_or(Zone::Local, Zone::Named)
+            })
+            .collect();
+
+        // A day either side covers every author's timezone; the exact
+        // range is applied per worklog below.
+        let after = from - Days::new(1);
+        let before = to + Days::new(1);
+        let ids: Vec<String> = authors
+            .iter()
+            .map(|a| format!("\"{}\"", a.account_id))
+            .collect();
+        let jql = format!(
+            "worklogAuthor in ({}) AND worklogDate >= \"{after}\" AND worklogDate <= \"{before}\" ORDER BY key ASC",
+            ids.join(", ")
+        );
+        let issues = self.search_all(&jql, &TIMESHEET_FIELDS).await?;
+        let worklogs: Vec<Vec<Worklog>> = stream::iter(&issues)
+            .map(|issue| {
+                self.worklogs(
+                    &issue.key,
+                    start_of_day(after),
+                    start_of_day(before + Days::new(1)),
+                )
+            })
+            .buffered(WORKLOG_CONCURRENCY)
+            .try_collect()
+            .await?;
+        let epics = match group_by {
+            GroupBy::Epic => self.epic_labels(&issues).await?,
+            _ => Vec::new(),
+        };
+
+        let mut groups: Vec<String> = Vec::new();
+        if group_by == GroupBy::Day {
+            let mut date = from;
+            while date <= to {
+                groups.push(date.format("%Y-%m-%d").to_string());
+                date = date + Days::new(1);
+            }
+        }
+        let mut seconds: Vec<Vec<i64>> = vec![vec![0; authors.len()]; groups.len()];
+        for (index, (issue, worklogs)) in issues.iter().zip(&worklogs).enumerate() {
+            for worklog in worklogs {
+                let author_id = worklog.author.as_ref().map(|a| a.account_id.as_str());
+                let Some(user) = authors
+                    .iter()
+                    .position(|a| Some(a.account_id.as_str()) == author_id)
+                else {
+                    continue;
+                };
+                let Some(started) = parse_jira_time(&worklog.started) else {
+                    continue;
+                };
+                let date = zones[user].date_of(started);
+                if date < from || date > to {
+                    continue;
+                }
+                let group = match group_by {
+                    GroupBy::Day => date.format("%Y-%m-%d").to_string(),
+                    GroupBy::Issue => label(issue),
+                    GroupBy::Epic => epics[index].clone(),
+                };
+                let row = match groups.iter().position(|g| *g == group) {
+                    Some(row) => row,
+                    None => {
+                        groups.push(group);
+                        seconds.push(vec![0; authors.len()]);
+                        groups.len() - 1
+                    }
+                };
+                seconds[row][user] += worklog.time_spent_seconds;
+            }
+        }
+        if let Some(row) = groups.iter().position(|g| g == NO_EPIC) {
+            let group = groups.remove(row);
+            groups.push(group);
+            let spent = seconds.remove(row);
+            seconds.push(spent);
+        }
+
+        let rows = groups
+            .into_iter()
+            .zip(&seconds)
+            .map(|(group, spent)| TimesheetRow {
+                group,
+                hours: spent.iter().copied().map(hours).collect(),
+                total: hours(spent.iter().sum()),
+            })
+            .collect();
+        let per_user: Vec<i64> = (0..authors.len())
+            .map(|user| seconds.iter().map(|row| row[user]).sum())
+            .collect();
+        Ok(Timesheet {
+            users: authors.into_iter().map(|a| a.display_name).collect(),
+            rows,
+            totals: per_user.iter().copied().map(hours).collect(),
+            total: hours(per_user.iter().sum()),
+        })
+    }
+
+    /// The epic label of each issue: the issue itself for epics, its parent,
+    /// or for sub-tasks its parent's parent.
+    async fn epic_labels(&self, issues: &[Issue]) -> Result<Vec<String>> {
+        let subtask_parents: Vec<String> = issues
+            .iter()
+            .filter(|issue| {
+                issue
+                    .fields
+                    .issuetype
+                    .as_ref()
+                    .is_some_and(|t| hierarchy_level(t) < 0)
+            })
+            .filter_map(|issue| issue.fields.parent.as_ref().map(|p| p.key.clone()))
+            .collect();
+        let parents = if subtask_parents.is_empty() {
+            Vec::new()
+        } else {
+            self.search_keys("key", &subtask_parents, &["parent"])
+                .await?
+        };
+        Ok(issues
+            .iter()
+            .map(|issue| {
+                let level = issue.fields.issuetype.as_ref().map_or(0, hierarchy_level);
+                let parent = issue.fields.parent.as_deref();
+                let epic = match level {
+                    1.. => Some(issue),
+                    0 => parent,
+                    _ => parent
+                        .and_then(|p| parents.iter().find(|found| found.key == p.key))
+                        .and_then(|found| found.fields.parent.as_deref()),
+                };
+                epic.map_or_else(|| NO_EPIC.to_string(), label)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_client;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn timesheet_buckets_days_in_each_authors_timezone() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/api/3/user/search")
+                .query_param("query", "ada@example.com");
+            then.status(200)
+                .json_body(json!([{ "accountId": "a1", "displayName": "Ada",
+                "emailAddress": "ada@example.com", "timeZone": "Europe/Berlin" }]));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/myself");
+            then.status(200)
+                .json_body(json!({ "accountId": "b2", "displayName": "Bob",
+                "timeZone": "America/New_York" }));
+        });
+        let search = server.mock(|when, then| {
+            when.method(POST).path("/rest/api/3/search/jql").json_body_partial(
+                r#"{ "jql": "worklogAuthor in (\"a1\", \"b2\") AND worklogDate >= \"2026-09-30\" AND worklogDate <= \"2026-10-03\" ORDER BY key ASC" }"#,
+            );
+            then.status(200).json_body(json!({ "isLast": true, "issues": [
+                { "key": "ACME-1", "fields": { "summary": "Login" } }
+            ] }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-1/worklog");
+            then.status(200).json_body(json!({ "total": 4, "worklogs": [
+                // 01:30 on 2 October in Berlin.
+                { "id": "1", "author": { "accountId": "a1" },
+                  "started": "2026-10-01T23:30:00.000+0000", "timeSpentSeconds": 3600 },
+                // 20:00 on 1 October in New York.
+                { "id": "2", "author": { "accountId": "b2" },
+                  "started": "2026-10-02T00:00:00.000+0000", "timeSpentSeconds": 5400 },
+                // 3 October in Berlin: outside the range.
+                { "id": "3", "author": { "accountId": "a1" },
+                  "started": "2026-10-02T22:30:00.000+0000", "timeSpentSeconds": 600 },
+                { "id": "4", "author": { "accountId": "c3" },
+                  "started": "2026-10-01T12:00:00.000+0000", "timeSpentSeconds": 600 }
+            ] }));
+        });
+        let client = test_client(&server);
+        let day = |d: u32| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+
+        let sheet = client
+            .timesheet(
+                &["ada@example.com".to_string(), "me".to_string()],
+                day(1),
+                day(2),
+                GroupBy::Day,
+                None,
+            )
+            .await
+            .unwrap();
+
+        search.assert();
+        assert_eq!(sheet.users, ["Ada", "Bob"]);
+        let rows: Vec<(&str, &[f64])> = sheet
+            .rows
+            .iter()
+            .map(|r| (r.group.as_str(), r.hours.as_slice()))
+            .collect();
+        assert_eq!(
+            rows,
+            [("2026-10-01", &[0.0, 1.5][..]), ("2026-10-02", &[1.0, 0.0])]
+        );
+        assert_eq!(sheet.total, 2.5);
+    }
+}
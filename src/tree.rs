@@ -0,0 +1,257 @@
+//! Recursive issue hierarchy: epic → children → sub-tasks, optionally
+//! following issue links as well.
+//!
+//! The tree is fetched one level at a time with a single `parent in (...)`
+//! search per level rather than one request per issue.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::client::{JiraClient, Request};
+use crate::models::Issue;
+
+/// Fields fetched for every node of the tree.
+pub const TREE_FIELDS: [&str; 6] = [
+    "summary",
+    "status",
+    "assignee",
+    "issuetype",
+    "parent",
+    "issuelinks",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeOptions {
+    /// Levels below the root to expand; 0 shows the root only.
+    pub depth: u32,
+    /// Also expand issues linked to each node.
+    pub links: bool,
+}
+
+/// One issue in the tree with its nested children.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueTree {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_category: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    /// Set when the node hangs off its parent through a link, e.g. `is blocked by`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// Already shown elsewhere in the tree, so not expanded again.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub repeated: bool,
+    pub children: Vec<IssueTree>,
+}
+
+impl IssueTree {
+    fn leaf(issue: &Issue, link: Option<String>, repeated: bool) -> Self {
+        IssueTree {
+            key: issue.key.clone(),
+            summary: issue.fields.summary.clone(),
+            issue_type: issue.fields.issuetype.as_ref().map(|t| t.name.clone()),
+            status: issue.status_name().map(str::to_string),
+            status_category: issue.status_category().map(str::to_string),
+            assignee: issue
+                .fields
+                .assignee
+                .as_ref()
+                .map(|a| a.display_name.clone()),
+            link,
+            repeated,
+            children: Vec::new(),
+        }
+    }
+}
+
+/// A node while the tree is being built level by level; children are indices.
+struct Slot {
+    issue: Issue,
+    link: Option<String>,
+    repeated: bool,
+    children: Vec<usize>,
+}
+
+impl JiraClient {
+    pub async fn issue_tree(&self, key: &str, options: TreeOptions) -> Result<IssueTree> {
+        let root: Issue = self
+            .execute_as(
+                Request::get(format!("issue/{key}"))
+                    .query("fields", TREE_FIELDS.join(","))
+                    .action("get issue"),
+            )
+            .await?;
+        let mut seen = HashSet::from([root.key.clone()]);
+        let mut slots = vec![Slot {
+            issue: root,
+            link: None,
+            repeated: false,
+            children: Vec::new(),
+        }];
+        let mut frontier = vec![0];
+
+        for _ in 0..options.depth {
+            if frontier.is_empty() {
+                break;
+            }
+            let keys: Vec<String> = frontier
+                .iter()
+                .map(|&i| slots[i].issue.key.clone())
+                .collect();
+            let mut children: HashMap<String, Vec<Issue>> = HashMap::new();
+            for child in self.search_keys("parent", &keys, &TREE_FIELDS).await? {
+                let parent = child.fields.parent.as_ref().map(|p| p.key.clone());
+                children
+                    .entry(parent.unwrap_or_default())
+                    .or_default()
+                    .push(child);
+            }
+
+            let mut links: HashMap<usize, Vec<(String, Issue)>> = HashMap::new();
+            if options.links {
+                for &i in &frontier {
+                    let issue_links = slots[i].issue.fields.issuelinks.iter().flatten();
+                    let targets = issue_links
+                        .filter_map(|link| link.target())
+                        .map(|(verb, other)| (verb.to_string(), other.clone()));
+                    links.entry(i).or_default().extend(targets);
+                }
+                // Links only carry a summary and status; fetch the rest for unseen targets.
+                let mut unseen: Vec<String> = links
+                    .values()
+                    .flatten()
+                    .map(|(_, issue)| issue.key.clone())
+                    .filter(|key| !seen.contains(key))
+                    .collect();
+                unseen.sort();
+                unseen.dedup();
+                let full: HashMap<String, Issue> = self
+                    .search_keys("key", &unseen, &TREE_FIELDS)
+                    .await?
+                    .into_iter()
+                    .map(|issue| (issue.key.clone(), issue))
+                    .collect();
+                for (_, issue) in links.values_mut().flatten() {
+                    if let Some(found) = full.get(&issue.key) {
+                        *issue = found.clone();
+                    }
+                }
+            }
+
+            let mut next = Vec::new();
+            for i in frontier {
+                let key = slots[i].issue.key.clone();
+                let kids = children.remove(&key).unwrap_or_default();
+                let linked = links.remove(&i).unwrap_or_default();
+                let entries = kids
+                    .into_iter()
+                    .map(|issue| (None, issue))
+                    .chain(linked.into_iter().map(|(verb, issue)| (Some(verb), issue)));
+                for (link, issue) in entries {
+                    let repeated = !seen.insert(issue.key.clone());
+                    let index = slots.len();
+                    slots.push(Slot {
+                        issue,
+                        link,
+                        repeated,
+                        children: Vec::new(),
+                    });
+                    slots[i].children.push(index);
+                    if !repeated {
+                        next.push(index);
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        Ok(nest(&slots, 0))
+    }
+}
+
+fn nest(slots: &[Slot], index: usize) -> IssueTree {
+    let slot = &slots[index];
+    let mut tree = IssueTree::leaf(&slot.issue, slot.link.clone(), slot.repeated);
+    tree.children = slot.children.iter().map(|&i| nest(slots, i)).collect();
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_client;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn issue_tree_walks_levels_and_stops_at_repeated_links() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/api/3/issue/ACME-1");
+            then.status(200).json_body(json!({
+                "key": "ACME-1",
+                "fields": { "summary": "Epic", "issuetype": { "name": "Epic" } }
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/search/jql")
+                .json_body_partial(r#"{ "jql": "parent in (ACME-1) ORDER BY key ASC" }"#);
+            then.status(200).json_body(json!({
+                "isLast": true,
+                "issues": [{
+                    "key": "ACME-2",
+                    "fields": {
+                        "summary": "Story",
+                        "parent": { "key": "ACME-1" },
+                        "issuelinks": [{
+                            "type": { "name": "Blocks", "inward": "is blocked by", "outward": "blocks" },
+                            "inwardIssue": { "key": "ACME-1" }
+                        }]
+                    }
+                }]
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/search/jql")
+                .json_body_partial(r#"{ "jql": "parent in (ACME-2) ORDER BY key ASC" }"#);
+            then.status(200).json_body(json!({
+                "isLast": true,
+                "issues": [{ "key": "ACME-3", "fields": { "parent": { "key": "ACME-2" } } }]
+            }));
+        });
+
+        let tree = test_client(&server)
+            .issue_tree(
+                "ACME-1",
+                TreeOptions {
+                    depth: 2,
+                    links: true,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(tree.key, "ACME-1");
+        let story = &tree.children[0];
+        assert_eq!(story.key, "ACME-2");
+        assert_eq!(story.children.len(), 2);
+        assert_eq!(story.children[0].key, "ACME-3");
+        let back = &story.children[1];
+        assert_eq!(back.key, "ACME-1");
+        assert_eq!(back.link.as_deref(), Some("is blocked by"));
+        assert!(back.repeated);
+        assert!(back.children.is_empty());
+    }
+}
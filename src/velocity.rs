@@ -0,0 +1,416 @@
+//! Sprint velocity and burndown, reconstructed from issue changelogs.
+//!
+//! Like Jira's own charts, an issue counts as done once it sits in the
+//! board's rightmost column, and the board's estimation field supplies the
+//! points. Sprint membership, status and points at any moment come from
+//! replaying the changelog backwards from the current values. Issues removed
+//! from a sprint no longer show up in its search and are left out.
+
+use anyhow::Result;
+use chrono::{DateTime, Days, FixedOffset, NaiveDate, NaiveTime, Utc};
+use serde::Serialize;
+
+use crate::changelog::{ChangeSet, next_change, parse_jira_time};
+use crate::client::{JiraClient, Request};
+use crate::epic::story_points;
+use crate::error::{ErrorKind, JiraError};
+use crate::models::{Issue, Sprint};
+
+/// Changelog field holding sprint ids, e.g. `"12, 13"`.
+const SPRINT_FIELD: &str = "Sprint";
+
+/// Committed and completed points of one closed sprint.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintVelocity {
+    pub id: u64,
+    pub name: String,
+    pub start_date: Option<String>,
+    pub complete_date: Option<String>,
+    /// Points in the sprint when it started.
+    pub committed: f64,
+    /// Points done in the sprint when it closed.
+    pub completed: f64,
+}
+
+/// Remaining points at the end of one sprint day.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BurndownDay {
+    /// `YYYY-MM-DD`.
+    pub date: String,
+    pub remaining: f64,
+    /// The straight line from the committed points down to zero at the sprint end.
+    pub ideal: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Burndown {
+    pub sprint: Sprint,
+    pub committed: f64,
+    pub days: Vec<BurndownDay>,
+}
+
+/// What a board counts as done and how it estimates.
+#[derive(Debug, Clone, PartialEq)]
+struct BoardSetup {
+    /// Status ids of the rightmost column.
+    done_statuses: Vec<String>,
+    point_fields: Vec<String>,
+}
+
+/// An issue of a sprint with its history.
+struct Tracked {
+    issue: Issue,
+    changes: Vec<ChangeSet>,
+}
+
+impl Tracked {
+    fn existed_at(&self, at: DateTime<FixedOffset>) -> bool {
+        let created = self
+            .issue
+            .fields
+            .created
+            .as_deref()
+            .and_then(parse_jira_time);
+        created.is_none_or(|created| created <= at)
+    }
+
+    fn in_sprint_at(&self, sprint: u64, at: DateTime<FixedOffset>) -> bool {
+        if !self.existed_at(at) {
+            return false;
+        }
+        match next_change(&self.changes, SPRINT_FIELD, at) {
+            Some(item) => item
+                .from
+                .as_deref()
+                .unwrap_or_default()
+                .split(',')
+                .any(|id| id.trim() == sprint.to_string()),
+            // Found by `sprint = id`, so it is in the sprint now.
+            None => true,
+        }
+    }
+
+    fn status_at(&self, at: DateTime<FixedOffset>) -> Option<String> {
+        match next_change(&self.changes, "status", at) {
+            Some(item) => item.from.clone(),
+            None => self.issue.fields.status.as_ref().map(|s| s.id.clone()),
+        }
+    }
+
+    fn points_at(&self, point_fields: &[String], at: DateTime<FixedOffset>) -> f64 {
+        let earlier = point_fields
+            .iter()
+            .find_map(|field| next_change(&self.changes, field, at));
+        let points = match earlier {
+            Some(item) => item
+                .from_text
+                .as_deref()
+                .and_then(|text| text.trim().parse().ok()),
+            None => story_points(&self.issue, point_fields),
+        };
+        points.unwrap_or(0.0)
+    }
+}
+
+/// Which issues of a sprint to count, by whether they were done at the time.
+#[derive(Clone, Copy, PartialEq)]
+enum Counting {
+    All,
+    Open,
+    Done,
+}
+
+/// Points of the issues in `sprint` at `at`.
+fn points_in_sprint(
+    issues: &[Tracked],
+    sprint: u64,
+    at: DateTime<FixedOffset>,
+    setup: &BoardSetup,
+    counting: Counting,
+) -> f64 {
+    issues
+        .iter()
+        .filter(|t| t.in_sprint_at(sprint, at))
+        .filter(|t| {
+            let done = t
+                .status_at(at)
+                .is_some_and(|status| setup.done_statuses.contains(&status));
+            match counting {
+                Counting::All => true,
+                Counting::Open => !done,
+                Counting::Done => done,
+            }
+        })
+        .map(|t| t.points_at(&setup.point_fields, at))
+        // Not `sum()`: an empty f64 sum is -0.0.
+        .fold(0.0, |total, points| total + points)
+}
+
+/// The start of `date` in the timezone of `like`.
+fn midnight(date: NaiveDate, like: &DateTime<FixedOffset>) -> DateTime<FixedOffset> {
+    let start = date.and_time(NaiveTime::MIN);
+    DateTime::from_naive_utc_and_offset(start - *like.offset(), *like.offset())
+}
+
+fn sprint_start(sprint: &Sprint) -> Result<DateTime<FixedOffset>> {
+    sprint
+        .start_date
+        .as_deref()
+        .and_then(parse_jira_time)
+        .ok_or_else(|| {
+            JiraError::new(
+                ErrorKind::Validation,
+                format!("Sprint {} ({}) has not started", sprint.id, sprint.name),
+            )
+            .into()
+        })
+}
+
+/// When the sprint closed, or is due to.
+fn sprint_end(sprint: &Sprint) -> Option<DateTime<FixedOffset>> {
+    sprint
+        .complete_date
+        .as_deref()
+        .or(sprint.end_date.as_deref())
+        .and_then(parse_jira_time)
+}
+
+impl JiraClient {
+    async fn board_setup(&self, board_id: u64) -> Result<BoardSetup> {
+        let config = self
+            .execute(
+                Request::get(format!("board/{board_id}/configuration"))
+                    .agile()
+                    .action("get board configuration"),
+            )
+            .await?;
+        let done_statuses = config["columnConfig"]["columns"]
+            .as_array()
+            .and_then(|columns| columns.last())
+            .and_then(|column| column["statuses"].as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|status| status["id"].as_str().map(str::to_string))
+            .collect();
+        let point_fields = match config["estimation"]["field"]["fieldId"].as_str() {
+            Some(field) => vec![field.to_string()],
+            None => self.story_point_fields().await?,
+        };
+        Ok(BoardSetup {
+            done_statuses,
+            point_fields,
+        })
+    }
+
+    async fn tracked_sprint_issues(
+        &self,
+        sprint_id: u64,
+        setup: &BoardSetup,
+    ) -> Result<Vec<Tracked>> {
+        let mut fields = vec!["status", "created"];
+        fields.extend(setup.point_fields.iter().map(String::as_str));
+        let issues = self
+            .search_all(&format!("sprint = {sprint_id}"), &fields)
+            .await?;
+        let keys: Vec<String> = issues.iter().map(|issue| issue.key.clone()).collect();
+        let changelogs = self.changelogs(&keys).await?;
+        Ok(issues
+            .into_iter()
+            .zip(changelogs)
+            .map(|(issue, changes)| Tracked { issue, changes })
+            .collect())
+    }
+
+    /// Committed vs. completed points of the board's `last` closed sprints, oldest first.
+    pub async fn velocity(&self, board_id: u64, last: usize) -> Result<Vec<SprintVelocity>> {
+        let setup = self.board_setup(board_id).await?;
+        let mut sprints = self.list_sprints(board_id, &["closed".to_string()]).await?;
+        sprints.retain(|sprint| sprint.start_date.is_some());
+        sprints.sort_by_key(sprint_end);
+        let skip = sprints.len().saturating_sub(last);
+
+        let mut velocity = Vec::new();
+        for sprint in sprints.into_iter().skip(skip) {
+            let issues = self.tracked_sprint_issues(sprint.id, &setup).await?;
+            let start = sprint_start(&sprint)?;
+            let end = sprint_end(&sprint).unwrap_or(start);
+            let committed = points_in_sprint(&issues, sprint.id, start, &setup, Counting::All);
+            let completed = points_in_sprint(&issues, sprint.id, end, &setup, Counting::Done);
+            velocity.push(SprintVelocity {
+                id: sprint.id,
+                name: sprint.name,
+                start_date: sprint.start_date,
+                complete_date: sprint.complete_date,
+                committed,
+                completed,
+            });
+        }
+        Ok(velocity)
+    }
+
+    /// Remaining points at the end of each day of the sprint, up to now for
+    /// an active one. Days follow the timezone of the sprint's start date.
+    pub async fn burndown(&self, sprint_id: u64) -> Result<Burndown> {
+        let sprint = self.get_sprint(sprint_id).await?;
+        let board_id = sprint.origin_board_id.ok_or_else(|| {
+            JiraError::new(
+                ErrorKind::Validation,
+                format!("Sprint {sprint_id} does not name the board it belongs to"),
+            )
+        })?;
+        let start = sprint_start(&sprint)?;
+        let now = Utc::now().with_timezone(start.offset());
+        let end = sprint_end(&sprint).unwrap_or(now);
+        let setup = self.board_setup(board_id).await?;
+        let issues = self.tracked_sprint_issues(sprint_id, &setup).await?;
+
+        let committed = points_in_sprint(&issues, sprint_id, start, &setup, Counting::Open);
+        let length = (end - start).num_seconds().max(1) as f64;
+        let mut days = Vec::new();
+        let last = end.min(now);
+        let mut date = start.date_naive();
+        while date <= last.date_naive() {
+            if date > start.date_naive() && midnight(date, &start) >= last {
+                break;
+            }
+            let next = date + Days::new(1);
+            let at = midnight(next, &start).min(last);
+            let elapsed = (at - start).num_seconds() as f64 / length;
+            days.push(BurndownDay {
+                date: date.format("%Y-%m-%d").to_string(),
+                remaining: points_in_sprint(&issues, sprint_id, at, &setup, Counting::Open),
+                ideal: ((committed * (1.0 - elapsed)).max(0.0) * 100.0).round() / 100.0,
+            });
+            date = next;
+        }
+        Ok(Burndown {
+            sprint,
+            committed,
+            days,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::test_client;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    /// Board 7 is done in status 3 and estimates in `customfield_10016`.
+    /// Sprint 5 ran 2026-10-01 to 2026-10-04; ACME-1 (3 points) was done on
+    /// day two, ACME-2 went from 2 to 5 points on day one and stayed open,
+    /// ACME-3 (1 point) was added on day three.
+    fn mock_sprint(server: &MockServer) {
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/agile/1.0/board/7/configuration");
+            then.status(200).json_body(json!({
+                "columnConfig": { "columns": [
+                    { "name": "To Do", "statuses": [{ "id": "1" }] },
+                    { "name": "Done", "statuses": [{ "id": "3" }] }
+                ] },
+                "estimation": { "type": "field", "field": { "fieldId": "customfield_10016" } }
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/rest/api/3/search/jql")
+                .json_body_partial(r#"{ "jql": "sprint = 5" }"#);
+            then.status(200)
+                .json_body(json!({ "isLast": true, "issues": [
+                { "key": "ACME-1", "fields": { "status": { "id": "3" },
+                    "created": "2026-09-20T00:00:00.000+0000", "customfield_10016": 3.0 } },
+                { "key": "ACME-2", "fields": { "status": { "id": "1" },
+                    "created": "2026-09-20T00:00:00.000+0000", "customfield_10016": 5.0 } },
+                { "key": "ACME-3", "fields": { "status": { "id": "1" },
+                    "created": "2026-09-20T00:00:00.000+0000", "customfield_10016": 1.0 } }
+            ] }));
+        });
+        let changelogs = [
+            (
+                "ACME-1",
+                json!([{ "id": "1", "created": "2026-10-02T10:00:00.000+0000",
+                "items": [{ "field": "status", "from": "1", "to": "3" }] }]),
+            ),
+            (
+                "ACME-2",
+                json!([{ "id": "2", "created": "2026-10-01T15:00:00.000+0000",
+                "items": [{ "field": "Story point estimate", "fieldId": "customfield_10016",
+                    "fromString": "2", "toString": "5" }] }]),
+            ),
+            (
+                "ACME-3",
+                json!([{ "id": "3", "created": "2026-10-03T09:00:00.000+0000",
+                "items": [{ "field": "Sprint", "from": "", "to": "5" }] }]),
+            ),
+        ];
+        for (key, values) in changelogs {
+            server.mock(|when, then| {
+                when.method(GET)
+                    .path(format!("/rest/api/3/issue/{key}/changelog"));
+                then.status(200)
+                    .json_body(json!({ "isLast": true, "values": values }));
+            });
+        }
+    }
+
+    #[tokio::test]
+    async fn velocity_compares_points_at_start_with_points_done_at_close() {
+        let server = MockServer::start();
+        mock_sprint(&server);
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/rest/agile/1.0/board/7/sprint")
+                .query_param("state", "closed");
+            then.status(200)
+                .json_body(json!({ "isLast": true, "values": [
+                { "id": 5, "name": "Sprint 5", "state": "closed",
+                  "startDate": "2026-10-01T09:00:00.000Z",
+                  "completeDate": "2026-10-04T17:00:00.000Z" },
+                { "id": 4, "name": "Sprint 4", "state": "closed",
+                  "startDate": "2026-09-17T09:00:00.000Z",
+                  "completeDate": "2026-09-30T17:00:00.000Z" }
+            ] }));
+        });
+
+        let velocity = test_client(&server).velocity(7, 1).await.unwrap();
+
+        assert_eq!(velocity.len(), 1);
+        assert_eq!(velocity[0].name, "Sprint 5");
+        assert_eq!(velocity[0].committed, 5.0);
+        assert_eq!(velocity[0].completed, 3.0);
+    }
+
+    #[tokio::test]
+    async fn burndown_replays_scope_estimates_and_status_per_day() {
+        let server = MockServer::start();
+        mock_sprint(&server);
+        server.mock(|when, then| {
+            when.method(GET).path("/rest/agile/1.0/sprint/5");
+            then.status(200).json_body(json!({
+                "id": 5, "name": "Sprint 5", "state": "closed", "originBoardId": 7,
+                "startDate": "2026-10-01T00:00:00.000Z",
+                "completeDate": "2026-10-04T00:00:00.000Z"
+            }));
+        });
+
+        let burndown = test_client(&server).burndown(5).await.unwrap();
+
+        assert_eq!(burndown.committed, 5.0);
+        let days: Vec<(&str, f64, f64)> = burndown
+            .days
+            .iter()
+            .map(|d| (d.date.as_str(), d.remaining, d.ideal))
+            .collect();
+        assert_eq!(
+            days,
+            [
+                ("2026-10-01", 8.0, 3.33),
+                ("2026-10-02", 5.0, 1.67),
+                ("2026-10-03", 6.0, 0.0),
+            ]
+        );
+    }
+}
@@ -9,6 +9,7 @@ fn base_env(server: &MockServer) -> Vec<(&'static str, String)> {
         ("JIRA_BASE_URL", server.base_url()),
         ("JIRA_EMAIL", "user@example.com".to_string()),
         ("JIRA_API_TOKEN", "token".to_string()),
+        ("JIRA_JOURNAL", "off".to_string()),
     ]
 }
 
@@ -299,3 +300,1424 @@ fn cli_link_clones_creates_cloners_issue_link() -> Result<()> {
     assert_eq!(value, json!({}));
     Ok(())
 }
+
+#[test]
+fn cli_whoami_prints_account_summary() -> Result<()> {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/myself");
+        then.status(200).json_body(json!({
+            "accountId": "abc",
+            "displayName": "Ada Lovelace",
+            "emailAddress": "user@example.com",
+            "timeZone": "Europe/London",
+            "active": true
+        }));
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd.arg("whoami").assert().success();
+
+    mock.assert();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    let value: Value = serde_json::from_str(&stdout)?;
+    assert_eq!(value["accountId"], "abc");
+    assert_eq!(value["displayName"], "Ada Lovelace");
+    assert_eq!(value["timeZone"], "Europe/London");
+    assert!(value.get("active").is_none());
+    Ok(())
+}
+
+#[test]
+fn cli_doctor_passes_all_checks() -> Result<()> {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/serverInfo");
+        then.status(200)
+            .json_body(json!({ "serverTitle": "Acme Jira" }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/myself");
+        then.status(200).json_body(json!({ "displayName": "Ada" }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/project/ACME");
+        then.status(200)
+            .json_body(json!({ "key": "ACME", "name": "Acme" }));
+    });
+    let permissions = server.mock(|when, then| {
+        when.method(GET)
+            .path("/rest/api/3/mypermissions")
+            .query_param("projectKey", "ACME")
+            .query_param(
+                "permissions",
+                "BROWSE_PROJECTS,CREATE_ISSUES,EDIT_ISSUES,TRANSITION_ISSUES,LINK_ISSUES",
+            );
+        then.status(200).json_body(json!({
+            "permissions": {
+                "BROWSE_PROJECTS": { "havePermission": true },
+                "CREATE_ISSUES": { "havePermission": true },
+                "EDIT_ISSUES": { "havePermission": true },
+                "TRANSITION_ISSUES": { "havePermission": true },
+                "LINK_ISSUES": { "havePermission": true }
+            }
+        }));
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd
+        .arg("doctor")
+        .arg("--project")
+        .arg("ACME")
+        .assert()
+        .success();
+
+    permissions.assert();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    assert!(stdout.contains("[PASS] Authentication: logged in as Ada"));
+    assert!(stdout.contains("[PASS] Project: ACME (Acme)"));
+    assert!(!stdout.contains("[FAIL]"));
+    Ok(())
+}
+
+#[test]
+fn cli_doctor_reports_rejected_credentials() -> Result<()> {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/serverInfo");
+        then.status(200)
+            .json_body(json!({ "serverTitle": "Acme Jira" }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/myself");
+        then.status(401);
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd
+        .arg("doctor")
+        .arg("--project")
+        .arg("ACME")
+        .assert()
+        .failure();
+
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    assert!(stdout.contains("[FAIL] Authentication: Jira returned 401 Unauthorized"));
+    assert!(stdout.contains("hint: Check JIRA_EMAIL"));
+    assert!(stdout.contains("[SKIP] Project: not authenticated"));
+    Ok(())
+}
+
+#[test]
+fn cli_doctor_rejects_invalid_max_attempts() -> Result<()> {
+    let server = MockServer::start();
+    let server_info = server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/serverInfo");
+        then.status(200)
+            .json_body(json!({ "serverTitle": "Acme Jira" }));
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd
+        .env("JIRA_MAX_ATTEMPTS", "zero")
+        .arg("doctor")
+        .assert()
+        .failure();
+
+    server_info.assert_hits(0);
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    assert!(stdout.contains("[FAIL] Settings: JIRA_MAX_ATTEMPTS must be a positive integer"));
+    assert!(stdout.contains("[SKIP] Connectivity: configuration is incomplete"));
+    Ok(())
+}
+
+#[test]
+fn cli_not_found_exits_with_not_found_code_and_json_error() -> Result<()> {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-404");
+        then.status(404).json_body(json!({
+            "errorMessages": ["Issue does not exist or you do not have permission to see it."],
+            "errors": {}
+        }));
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd
+        .arg("view")
+        .arg("ACME-404")
+        .arg("--error-format")
+        .arg("json")
+        .assert()
+        .code(6);
+
+    let stderr = String::from_utf8(assert.get_output().stderr.clone())?;
+    let value: Value = serde_json::from_str(&stderr)?;
+    assert_eq!(value["error"]["kind"], "not_found");
+    assert_eq!(value["error"]["status"], 404);
+    assert_eq!(
+        value["error"]["messages"][0],
+        "Issue does not exist or you do not have permission to see it."
+    );
+    Ok(())
+}
+
+#[test]
+fn cli_missing_settings_exit_with_config_code() -> Result<()> {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    let assert = cmd
+        .env_remove("JIRA_BASE_URL")
+        .env("JIRA_EMAIL", "user@example.com")
+        .env("JIRA_API_TOKEN", "token")
+        .arg("list")
+        .assert()
+        .code(3);
+
+    let stderr = String::from_utf8(assert.get_output().stderr.clone())?;
+    assert!(stderr.contains("Missing JIRA_BASE_URL"));
+    Ok(())
+}
+
+#[test]
+fn cli_missing_project_key_exits_with_config_code() -> Result<()> {
+    let server = MockServer::start();
+
+    for args in [&["epic", "list"][..], &["list"][..]] {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+        for (key, val) in base_env(&server) {
+            cmd.env(key, val);
+        }
+        cmd.env_remove("JIRA_PROJECT_KEY")
+            .env_remove("JIRA_DEFAULT_JQL")
+            .args(args)
+            .assert()
+            .code(3);
+    }
+    Ok(())
+}
+
+#[test]
+fn cli_sprint_view_groups_issues_by_status() -> Result<()> {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/agile/1.0/sprint/12");
+        then.status(200)
+            .json_body(json!({ "id": 12, "name": "Sprint 12", "state": "active" }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/agile/1.0/sprint/12/issue");
+        then.status(200).json_body(json!({
+            "startAt": 0,
+            "total": 3,
+            "issues": [
+                { "key": "ACME-1", "fields": { "summary": "Done thing",
+                    "status": { "name": "Done", "statusCategory": { "key": "done" } } } },
+                { "key": "ACME-2", "fields": { "summary": "Open thing",
+                    "status": { "name": "To Do", "statusCategory": { "key": "new" } },
+                    "assignee": { "accountId": "abc", "displayName": "Ada" } } },
+                { "key": "ACME-3", "fields": { "summary": "Other open thing",
+                    "status": { "name": "To Do", "statusCategory": { "key": "new" } } } }
+            ]
+        }));
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd.arg("sprint").arg("view").arg("12").assert().success();
+
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    let value: Value = serde_json::from_str(&stdout)?;
+    assert_eq!(value["sprint"]["name"], "Sprint 12");
+    assert_eq!(value["statuses"][0]["status"], "To Do");
+    assert_eq!(value["statuses"][0]["count"], 2);
+    assert_eq!(value["statuses"][0]["issues"][0]["assignee"], "Ada");
+    assert_eq!(value["statuses"][1]["status"], "Done");
+    Ok(())
+}
+
+#[test]
+fn cli_sprint_add_moves_issues() -> Result<()> {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/agile/1.0/sprint/12/issue")
+            .json_body(json!({ "issues": ["ACME-1", "ACME-2"] }));
+        then.status(204);
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    cmd.arg("sprint")
+        .arg("add")
+        .arg("12")
+        .arg("ACME-1")
+        .arg("ACME-2")
+        .assert()
+        .success();
+
+    mock.assert();
+    Ok(())
+}
+
+#[test]
+fn cli_sprint_create_sends_board_name_and_dates() -> Result<()> {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/agile/1.0/sprint")
+            .json_body(json!({
+                "name": "Sprint 13",
+                "originBoardId": 7,
+                "startDate": "2026-10-19T09:00:00+00:00",
+                "endDate": "2026-11-02T09:00:00+00:00"
+            }));
+        then.status(201)
+            .json_body(json!({ "id": 13, "name": "Sprint 13", "state": "future" }));
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd
+        .args(["sprint", "create", "--board", "7", "--name", "Sprint 13"])
+        .args(["--start", "2026-10-19T09:00:00Z"])
+        .args(["--end", "2026-11-02T09:00:00Z"])
+        .assert()
+        .success();
+
+    mock.assert();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    let value: Value = serde_json::from_str(&stdout)?;
+    assert_eq!(value["id"], 13);
+    Ok(())
+}
+
+#[test]
+fn cli_sprint_close_moves_open_issues_to_backlog() -> Result<()> {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/agile/1.0/sprint/12");
+        then.status(200)
+            .json_body(json!({ "id": 12, "state": "active", "originBoardId": 7 }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/agile/1.0/sprint/12/issue");
+        then.status(200).json_body(json!({
+            "total": 2,
+            "issues": [
+                { "key": "ACME-1", "fields": { "status": { "name": "Done", "statusCategory": { "key": "done" } } } },
+                { "key": "ACME-2", "fields": { "status": { "name": "To Do", "statusCategory": { "key": "new" } } } }
+            ]
+        }));
+    });
+    let backlog = server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/agile/1.0/backlog/issue")
+            .json_body(json!({ "issues": ["ACME-2"] }));
+        then.status(204);
+    });
+    let closed = server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/agile/1.0/sprint/12")
+            .json_body(json!({ "state": "closed" }));
+        then.status(200)
+            .json_body(json!({ "id": 12, "state": "closed" }));
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd
+        .args(["sprint", "close", "12", "--move-open-to", "backlog"])
+        .assert()
+        .success();
+
+    backlog.assert();
+    closed.assert();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    let value: Value = serde_json::from_str(&stdout)?;
+    assert_eq!(value["moved"], json!(["ACME-2"]));
+    assert_eq!(value["movedTo"], Value::Null);
+    assert_eq!(value["completed"], json!(["ACME-1"]));
+    Ok(())
+}
+
+#[test]
+fn cli_epic_remove_skips_issues_of_other_epics() -> Result<()> {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-2");
+        then.status(200)
+            .json_body(json!({ "key": "ACME-2", "fields": { "parent": { "key": "ACME-1" } } }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-3");
+        then.status(200)
+            .json_body(json!({ "key": "ACME-3", "fields": { "parent": { "key": "ACME-9" } } }));
+    });
+    let cleared = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/rest/api/3/issue/ACME-2")
+            .json_body(json!({ "fields": { "parent": null } }));
+        then.status(204);
+    });
+    let untouched = server.mock(|when, then| {
+        when.method(PUT).path("/rest/api/3/issue/ACME-3");
+        then.status(204);
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd
+        .args(["epic", "remove", "ACME-1", "ACME-2", "ACME-3"])
+        .assert()
+        .failure();
+
+    cleared.assert();
+    untouched.assert_hits(0);
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    let value: Value = serde_json::from_str(&stdout)?;
+    assert_eq!(value["removed"], json!(["ACME-2"]));
+    assert_eq!(value["failed"]["ACME-3"], "ACME-3 is not in epic ACME-1");
+    Ok(())
+}
+
+#[test]
+fn cli_tree_prints_indented_hierarchy() -> Result<()> {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-1");
+        then.status(200).json_body(json!({
+            "key": "ACME-1",
+            "fields": {
+                "summary": "Login revamp",
+                "issuetype": { "name": "Epic" },
+                "status": { "name": "In Progress" },
+                "assignee": { "displayName": "Ada" }
+            }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/api/3/search/jql")
+            .json_body_partial(r#"{ "jql": "parent in (ACME-1) ORDER BY key ASC" }"#);
+        then.status(200).json_body(json!({
+            "isLast": true,
+            "issues": [
+                { "key": "ACME-2", "fields": { "summary": "Sign-in page", "issuetype": { "name": "Story" }, "status": { "name": "To Do" }, "parent": { "key": "ACME-1" } } },
+                { "key": "ACME-3", "fields": { "summary": "Sessions", "issuetype": { "name": "Story" }, "status": { "name": "Done" }, "parent": { "key": "ACME-1" } } }
+            ]
+        }));
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd
+        .args(["tree", "ACME-1", "--depth", "1"])
+        .assert()
+        .success();
+
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    assert_eq!(
+        stdout,
+        "ACME-1 [Epic] Login revamp (In Progress, Ada)\n\
+         ├── ACME-2 [Story] Sign-in page (To Do, unassigned)\n\
+         └── ACME-3 [Story] Sessions (Done, unassigned)\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn cli_graph_emits_mermaid_and_reports_loops() -> Result<()> {
+    let server = MockServer::start();
+    let blocks = json!({ "name": "Blocks", "inward": "is blocked by", "outward": "blocks" });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/api/3/search/jql")
+            .json_body_partial(
+                r#"{ "jql": "project = ACME", "fields": ["summary", "status", "issuelinks"] }"#,
+            );
+        then.status(200).json_body(json!({
+            "isLast": true,
+            "issues": [
+                { "key": "ACME-1", "fields": { "issuelinks": [{ "type": blocks, "outwardIssue": { "key": "ACME-2" } }] } },
+                { "key": "ACME-2", "fields": { "issuelinks": [{ "type": blocks, "outwardIssue": { "key": "ACME-1" } }] } }
+            ]
+        }));
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd
+        .args(["graph", "--jql", "project = ACME", "--format", "mermaid"])
+        .assert()
+        .success();
+
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    assert!(stdout.starts_with("flowchart LR\n"));
+    assert!(stdout.contains("  ACME_1 -->|blocks| ACME_2\n"));
+    assert!(stdout.contains("  ACME_2 -->|blocks| ACME_1\n"));
+    let stderr = String::from_utf8(assert.get_output().stderr.clone())?;
+    assert_eq!(stderr, "Blocking loop: ACME-1 -> ACME-2 -> ACME-1\n");
+    Ok(())
+}
+
+#[test]
+fn cli_blockers_follows_unresolved_blockers() -> Result<()> {
+    let server = MockServer::start();
+    let blocked_by = |key: &str, category: &str| {
+        json!({
+            "type": { "name": "Blocks", "inward": "is blocked by", "outward": "blocks" },
+            "inwardIssue": { "key": key, "fields": { "status": { "name": category, "statusCategory": { "key": category } } } }
+        })
+    };
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-1");
+        then.status(200).json_body(json!({
+            "key": "ACME-1",
+            "fields": { "issuelinks": [blocked_by("ACME-2", "new"), blocked_by("ACME-3", "done")] }
+        }));
+    });
+    let second_level = server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/api/3/search/jql")
+            .json_body_partial(r#"{ "jql": "key in (ACME-2) ORDER BY key ASC" }"#);
+        then.status(200).json_body(json!({
+            "isLast": true,
+            "issues": [{
+                "key": "ACME-2",
+                "fields": {
+                    "summary": "API",
+                    "status": { "name": "To Do", "statusCategory": { "key": "new" } },
+                    "issuelinks": []
+                }
+            }]
+        }));
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd.args(["blockers", "ACME-1"]).assert().success();
+
+    second_level.assert();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    let value: Value = serde_json::from_str(&stdout)?;
+    assert_eq!(
+        value,
+        json!([{
+            "key": "ACME-2",
+            "summary": "API",
+            "status": "To Do",
+            "statusCategory": "new",
+            "distance": 1,
+            "blocks": ["ACME-1"]
+        }])
+    );
+    Ok(())
+}
+
+#[test]
+fn cli_import_maps_csv_columns_and_attaches_subtasks() -> Result<()> {
+    let server = MockServer::start();
+    let stories = server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/api/3/issue/bulk")
+            .json_body_partial(
+                r#"{ "issueUpdates": [{ "fields": {
+                    "project": { "key": "ACME" },
+                    "summary": "Login page",
+                    "issuetype": { "name": "Story" },
+                    "labels": ["auth", "web"]
+                } }] }"#,
+            );
+        then.status(201)
+            .json_body(json!({ "issues": [{ "id": "1", "key": "ACME-1" }], "errors": [] }));
+    });
+    let subtasks = server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/api/3/issue/bulk")
+            .json_body_partial(
+                r#"{ "issueUpdates": [{ "fields": {
+                    "summary": "Form",
+                    "issuetype": { "name": "Sub-task" },
+                    "parent": { "key": "ACME-1" }
+                } }] }"#,
+            );
+        then.status(201)
+            .json_body(json!({ "issues": [{ "id": "2", "key": "ACME-2" }], "errors": [] }));
+    });
+    let file = std::env::temp_dir().join(format!("jico-import-{}.csv", std::process::id()));
+    std::fs::write(
+        &file,
+        "Ref,Title,Type,Parent,labels\n1,Login page,Story,,\"auth, web\"\n2,Form,,1,\n",
+    )?;
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd
+        .arg("import")
+        .arg(&file)
+        .args([
+            "--project",
+            "ACME",
+            "--map",
+            "Ref=id,Title=summary,Type=issue_type,Parent=parent",
+        ])
+        .assert()
+        .success();
+    std::fs::remove_file(&file)?;
+
+    stories.assert();
+    subtasks.assert();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    let value: Value = serde_json::from_str(&stdout)?;
+    assert_eq!(value["created"], 2);
+    assert_eq!(
+        value["rows"][1],
+        json!({ "row": 2, "id": "2", "summary": "Form", "key": "ACME-2" })
+    );
+    Ok(())
+}
+
+#[test]
+fn cli_import_sends_numeric_csv_cells_as_numbers() -> Result<()> {
+    let server = MockServer::start();
+    let create = server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/api/3/issue/bulk")
+            .json_body_partial(
+                r#"{ "issueUpdates": [{ "fields": {
+                    "summary": "42",
+                    "customfield_10016": 5,
+                    "customfield_10001": "Platform"
+                } }] }"#,
+            );
+        then.status(201)
+            .json_body(json!({ "issues": [{ "id": "1", "key": "ACME-1" }], "errors": [] }));
+    });
+    let file = std::env::temp_dir().join(format!("jico-import-points-{}.csv", std::process::id()));
+    std::fs::write(
+        &file,
+        "summary,Points,customfield_10001
+42,5,Platform
+",
+    )?;
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    cmd.arg("import")
+        .arg(&file)
+        .args(["--project", "ACME", "--map", "Points=customfield_10016"])
+        .assert()
+        .success();
+    std::fs::remove_file(&file)?;
+
+    create.assert();
+    Ok(())
+}
+
+#[test]
+fn cli_bulk_assign_asks_before_changing_issues() -> Result<()> {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/api/3/search/jql")
+            .json_body_partial(r#"{ "jql": "assignee = abc" }"#);
+        then.status(200).json_body(json!({
+            "isLast": true,
+            "issues": [
+                { "key": "ACME-1", "fields": { "summary": "One", "status": { "name": "To Do" } } },
+                { "key": "ACME-2", "fields": { "summary": "Two", "status": { "name": "Done" } } }
+            ]
+        }));
+    });
+    let assigned = server.mock(|when, then| {
+        when.method(PUT)
+            .path_matches(Regex::new("^/rest/api/3/issue/ACME-[12]/assignee$").unwrap())
+            .json_body(json!({ "accountId": "xyz" }));
+        then.status(204);
+    });
+    let bulk = || {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+        for (key, val) in base_env(&server) {
+            cmd.env(key, val);
+        }
+        cmd.args(["bulk", "--jql", "assignee = abc"]);
+        cmd
+    };
+
+    let mut declined = bulk();
+    declined.args(["assign", "--to", "xyz"]);
+    let declined = assert_cmd::Command::from_std(declined)
+        .write_stdin("n\n")
+        .assert()
+        .failure();
+    let stderr = String::from_utf8(declined.get_output().stderr.clone())?;
+    assert!(stderr.contains("2 issue(s) match.\n  ACME-1  One (To Do)\n  ACME-2  Two (Done)\n"));
+    assert!(stderr.contains("Aborted; no issues were changed"));
+    assigned.assert_hits(0);
+
+    let accepted = bulk()
+        .args(["--yes", "assign", "--to", "xyz"])
+        .assert()
+        .success();
+    assigned.assert_hits(2);
+    let stdout = String::from_utf8(accepted.get_output().stdout.clone())?;
+    let value: Value = serde_json::from_str(&stdout)?;
+    assert_eq!(value["succeeded"], 2);
+    assert_eq!(value["failures"], json!([]));
+    Ok(())
+}
+
+#[test]
+fn cli_bulk_resume_retries_only_failed_issues() -> Result<()> {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/rest/api/3/search/jql");
+        then.status(200).json_body(json!({
+            "isLast": true,
+            "issues": [{ "key": "ACME-1" }, { "key": "ACME-2" }]
+        }));
+    });
+    let first = server.mock(|when, then| {
+        when.method(PUT).path("/rest/api/3/issue/ACME-1/assignee");
+        then.status(204);
+    });
+    let mut broken = server.mock(|when, then| {
+        when.method(PUT).path("/rest/api/3/issue/ACME-2/assignee");
+        then.status(400)
+            .json_body(json!({ "errorMessages": ["User cannot be assigned"] }));
+    });
+    let checkpoint = std::env::temp_dir().join(format!("jico-bulk-{}.json", std::process::id()));
+    let jico = || {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+        for (key, val) in base_env(&server) {
+            cmd.env(key, val);
+        }
+        cmd
+    };
+
+    jico()
+        .args(["bulk", "--jql", "project = ACME", "--yes", "--checkpoint"])
+        .arg(&checkpoint)
+        .args(["assign", "--to", "xyz"])
+        .assert()
+        .failure();
+    let saved: Value = serde_json::from_str(&std::fs::read_to_string(&checkpoint)?)?;
+    assert_eq!(saved["kind"], "bulk");
+    assert_eq!(saved["done"], json!(["ACME-1"]));
+    assert!(saved["failed"]["ACME-2"].is_string());
+
+    broken.delete();
+    let fixed = server.mock(|when, then| {
+        when.method(PUT).path("/rest/api/3/issue/ACME-2/assignee");
+        then.status(204);
+    });
+    let assert = jico()
+        .args(["bulk", "--yes", "--retry-failed", "--resume"])
+        .arg(&checkpoint)
+        .assert()
+        .success();
+
+    first.assert_hits(1);
+    fixed.assert_hits(1);
+    assert!(!checkpoint.exists());
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    let value: Value = serde_json::from_str(&stdout)?;
+    assert_eq!(value["succeeded"], 2);
+    assert_eq!(value["failed"], 0);
+    Ok(())
+}
+
+#[test]
+fn cli_dry_run_prints_mutations_but_still_reads() -> Result<()> {
+    let server = MockServer::start();
+    let transitions = server.mock(|when, then| {
+        when.method(GET)
+            .path("/rest/api/3/issue/ACME-1/transitions");
+        then.status(200)
+            .json_body(json!({ "transitions": [{ "id": "31", "name": "Done" }] }));
+    });
+    let transition = server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/api/3/issue/ACME-1/transitions");
+        then.status(204);
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd
+        .args(["--dry-run", "transition", "ACME-1", "--to", "done"])
+        .assert()
+        .success();
+
+    transitions.assert();
+    transition.assert_hits(0);
+    let stderr = String::from_utf8(assert.get_output().stderr.clone())?;
+    let url = format!("{}/rest/api/3/issue/ACME-1/transitions", server.base_url());
+    assert!(stderr.starts_with(&format!("DRY RUN: POST {url}\n")));
+    let body: Value = serde_json::from_str(stderr.split_once('\n').unwrap().1)?;
+    assert_eq!(body, json!({ "transition": { "id": "31" } }));
+    Ok(())
+}
+
+#[test]
+fn cli_dry_run_sprint_create_and_add_report_plans() -> Result<()> {
+    let server = MockServer::start();
+    let writes = server.mock(|when, then| {
+        when.method(POST).path_contains("/rest/agile/1.0/sprint");
+        then.status(201).json_body(json!({ "id": 13 }));
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd
+        .args([
+            "--dry-run",
+            "sprint",
+            "create",
+            "--board",
+            "1",
+            "--name",
+            "X",
+        ])
+        .assert()
+        .success();
+    let stderr = String::from_utf8(assert.get_output().stderr.clone())?;
+    let url = format!("{}/rest/agile/1.0/sprint", server.base_url());
+    assert!(stderr.starts_with(&format!("DRY RUN: POST {url}\n")));
+    assert_eq!(
+        String::from_utf8(assert.get_output().stdout.clone())?,
+        "null\n"
+    );
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd
+        .args(["--dry-run", "sprint", "add", "13", "ACME-1"])
+        .assert()
+        .success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    let value: Value = serde_json::from_str(&stdout)?;
+    assert_eq!(value, json!({ "sprint": 13, "planned": ["ACME-1"] }));
+
+    writes.assert_hits(0);
+    Ok(())
+}
+
+#[test]
+fn cli_dry_run_import_plans_every_row_including_subtasks() -> Result<()> {
+    let server = MockServer::start();
+    let create = server.mock(|when, then| {
+        when.method(POST).path("/rest/api/3/issue/bulk");
+        then.status(201)
+            .json_body(json!({ "issues": [], "errors": [] }));
+    });
+    let file = std::env::temp_dir().join(format!("jico-import-dry-{}.csv", std::process::id()));
+    std::fs::write(&file, "id,summary,parent\n1,Login page,\n2,Form,1\n")?;
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd
+        .arg("--dry-run")
+        .arg("import")
+        .arg(&file)
+        .args(["--project", "ACME"])
+        .assert()
+        .success();
+    std::fs::remove_file(&file)?;
+
+    create.assert_hits(0);
+    let stderr = String::from_utf8(assert.get_output().stderr.clone())?;
+    assert_eq!(stderr.matches("DRY RUN: POST").count(), 2);
+    assert!(stderr.contains(r#""key": "(row 1)""#));
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    let value: Value = serde_json::from_str(&stdout)?;
+    assert_eq!(value["planned"], 2);
+    assert_eq!(value["failed"], 0);
+    assert_eq!(value["rows"][1]["key"], "(row 2)");
+    Ok(())
+}
+
+#[test]
+fn cli_history_and_undo_move_a_transitioned_issue_back() -> Result<()> {
+    let server = MockServer::start();
+    let journal =
+        std::env::temp_dir().join(format!("jico-cli-journal-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&journal);
+    let transitions = server.mock(|when, then| {
+        when.method(GET)
+            .path("/rest/api/3/issue/ACME-1/transitions");
+        then.status(200).json_body(json!({ "transitions": [
+            { "id": "21", "name": "Start", "to": { "name": "In Progress" } },
+            { "id": "11", "name": "Reopen", "to": { "name": "To Do" } }
+        ] }));
+    });
+    let status = server.mock(|when, then| {
+        when.method(GET)
+            .path("/rest/api/3/issue/ACME-1")
+            .query_param("fields", "status");
+        then.status(200)
+            .json_body(json!({ "key": "ACME-1", "fields": { "status": { "name": "To Do" } } }));
+    });
+    let start = server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/api/3/issue/ACME-1/transitions")
+            .json_body(json!({ "transition": { "id": "21" } }));
+        then.status(204);
+    });
+    let reopen = server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/api/3/issue/ACME-1/transitions")
+            .json_body(json!({ "transition": { "id": "11" } }));
+        then.status(204);
+    });
+    let run = |args: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+        for (key, val) in base_env(&server) {
+            cmd.env(key, val);
+        }
+        cmd.env("JIRA_JOURNAL", &journal)
+            .args(args)
+            .assert()
+            .success()
+    };
+
+    run(&["transition", "ACME-1", "--to", "start"]);
+    start.assert();
+    let history = run(&["history"]);
+    let history: Value = serde_json::from_slice(&history.get_output().stdout)?;
+    assert_eq!(history[0]["id"], 1);
+    assert_eq!(history[0]["command"], "jico transition ACME-1 --to start");
+    assert_eq!(history[0]["changes"][0]["from"], "To Do");
+
+    let undo = run(&["undo"]);
+    let outcomes: Value = serde_json::from_slice(&undo.get_output().stdout)?;
+    reopen.assert();
+    transitions.assert_hits(3);
+    status.assert_hits(2);
+    assert_eq!(
+        outcomes,
+        json!([{ "key": "ACME-1", "action": "move back to To Do" }])
+    );
+    let history = run(&["history"]);
+    let history: Value = serde_json::from_slice(&history.get_output().stdout)?;
+    std::fs::remove_file(&journal)?;
+    assert_eq!(history[0]["undoes"], 1);
+    assert_eq!(history[1]["undoneBy"], 2);
+    Ok(())
+}
+
+#[test]
+fn cli_delete_asks_first_and_deletes_subtasks_on_request() -> Result<()> {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/rest/api/3/issue/ACME-1")
+            .query_param("fields", "summary,status,subtasks");
+        then.status(200).json_body(json!({
+            "key": "ACME-1",
+            "fields": {
+                "summary": "Old spike",
+                "status": { "name": "To Do" },
+                "subtasks": [{ "key": "ACME-2" }]
+            }
+        }));
+    });
+    let delete = server.mock(|when, then| {
+        when.method(DELETE)
+            .path("/rest/api/3/issue/ACME-1")
+            .query_param("deleteSubtasks", "true");
+        then.status(204);
+    });
+    let delete_cmd = |answer: &str| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+        for (key, val) in base_env(&server) {
+            cmd.env(key, val);
+        }
+        cmd.args(["delete", "ACME-1", "--delete-subtasks"]);
+        assert_cmd::Command::from_std(cmd)
+            .write_stdin(answer)
+            .assert()
+    };
+
+    let declined = delete_cmd("n\n").failure();
+    let stderr = String::from_utf8(declined.get_output().stderr.clone())?;
+    assert!(stderr.starts_with("ACME-1  Old spike (To Do)\n  sub-tasks (deleted too): ACME-2\n"));
+    assert!(stderr.contains("Aborted; ACME-1 was not deleted"));
+    delete.assert_hits(0);
+
+    let accepted = delete_cmd("y\n").success();
+    delete.assert();
+    let value: Value = serde_json::from_slice(&accepted.get_output().stdout)?;
+    assert_eq!(
+        value,
+        json!({ "deleted": "ACME-1", "subtasks": ["ACME-2"] })
+    );
+    Ok(())
+}
+
+#[test]
+fn cli_merge_closes_the_duplicate_and_unions_labels() -> Result<()> {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-2");
+        then.status(200).json_body(json!({
+            "key": "ACME-2",
+            "fields": { "labels": ["login", "ui"], "issuelinks": [] }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-1");
+        then.status(200).json_body(json!({
+            "key": "ACME-1",
+            "fields": { "labels": ["backend", "login"] }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/rest/api/3/issue/ACME-2/transitions");
+        then.status(200).json_body(json!({ "transitions": [
+            { "id": "41", "name": "Close", "to": { "name": "Closed",
+                "statusCategory": { "key": "done" } },
+              "fields": { "resolution": { "allowedValues": [{ "name": "Duplicate" }] } } }
+        ] }));
+    });
+    let link = server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/api/3/issueLink")
+            .json_body_partial(r#"{ "type": { "name": "Duplicate" } }"#);
+        then.status(201);
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-2/comment");
+        then.status(200).json_body(json!({ "comments": [] }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-2/watchers");
+        then.status(200).json_body(json!({ "watchers": [] }));
+    });
+    let labels = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/rest/api/3/issue/ACME-1")
+            .json_body(json!({ "fields": { "labels": ["backend", "login", "ui"] } }));
+        then.status(204);
+    });
+    let close = server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/api/3/issue/ACME-2/transitions")
+            .json_body(json!({
+                "transition": { "id": "41" },
+                "fields": { "resolution": { "name": "Duplicate" } }
+            }));
+        then.status(204);
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let assert = cmd
+        .args(["merge", "ACME-2", "--into", "ACME-1"])
+        .assert()
+        .success();
+
+    link.assert();
+    labels.assert();
+    close.assert();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    let value: Value = serde_json::from_str(&stdout)?;
+    assert_eq!(
+        value,
+        json!({
+            "duplicate": "ACME-2",
+            "into": "ACME-1",
+            "comments": 0,
+            "attachments": [],
+            "watchers": 0,
+            "labels": ["ui"],
+            "status": "Closed",
+            "resolution": "Duplicate"
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn cli_log_prints_the_status_timeline_in_local_time() -> Result<()> {
+    let server = MockServer::start();
+    let changelog = server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-1/changelog");
+        then.status(200).json_body(json!({
+            "startAt": 0, "total": 2, "isLast": true,
+            "values": [
+                {
+                    "id": "2",
+                    "author": { "accountId": "a1", "displayName": "Ada" },
+                    "created": "2026-10-02T10:00:00.000+0200",
+                    "items": [{ "field": "status", "fieldId": "status",
+                        "fromString": "In Progress", "toString": "Done" }]
+                },
+                {
+                    "id": "1",
+                    "author": { "accountId": "a1", "displayName": "Ada" },
+                    "created": "2026-10-01T09:30:00.000+0000",
+                    "items": [
+                        { "field": "status", "fieldId": "status",
+                          "fromString": "To Do", "toString": "In Progress" },
+                        { "field": "assignee", "fieldId": "assignee", "toString": "Ada" }
+                    ]
+                }
+            ]
+        }));
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let output = cmd
+        .env("TZ", "UTC")
+        .args(["log", "ACME-1", "--field", "status"])
+        .assert()
+        .success();
+
+    changelog.assert();
+    assert_eq!(
+        String::from_utf8(output.get_output().stdout.clone())?,
+        "2026-10-01 09:30  Ada  status: To Do -> In Progress\n\
+         2026-10-02 08:00  Ada  status: In Progress -> Done\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn cli_report_cycle_time_writes_csv_with_percentiles() -> Result<()> {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/rest/api/3/search/jql");
+        then.status(200).json_body(json!({
+            "issues": [{
+                "key": "ACME-1",
+                "fields": {
+                    "issuetype": { "name": "Bug" },
+                    "status": { "name": "Done" },
+                    "created": "2026-10-01T00:00:00.000+0000"
+                }
+            }],
+            "isLast": true
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/rest/api/3/issue/ACME-1/changelog");
+        then.status(200).json_body(json!({
+            "total": 2, "isLast": true,
+            "values": [
+                { "id": "1", "created": "2026-10-01T12:00:00.000+0000",
+                  "items": [{ "field": "status", "fromString": "To Do", "toString": "In Progress" }] },
+                { "id": "2", "created": "2026-10-03T12:00:00.000+0000",
+                  "items": [{ "field": "status", "fromString": "In Progress", "toString": "Done" }] }
+            ]
+        }));
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let output = cmd
+        .args([
+            "report",
+            "cycle-time",
+            "--jql",
+            "project = ACME",
+            "--format",
+            "csv",
+        ])
+        .assert()
+        .success();
+
+    let csv = String::from_utf8(output.get_output().stdout.clone())?;
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[..3],
+        [
+            "Key,Type,Status,Cycle (d),Lead (d),To Do (d),In Progress (d)",
+            "ACME-1,Bug,Done,2.00,2.50,0.50,2.00",
+            "p50,All,,2.00,2.50,,",
+        ]
+    );
+    assert_eq!(lines.len(), 8);
+    Ok(())
+}
+
+#[test]
+fn cli_report_burndown_writes_remaining_points_per_day() -> Result<()> {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/agile/1.0/sprint/5");
+        then.status(200).json_body(json!({
+            "id": 5, "name": "Sprint 5", "state": "closed", "originBoardId": 7,
+            "startDate": "2026-10-01T00:00:00.000Z",
+            "completeDate": "2026-10-03T00:00:00.000Z"
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/rest/agile/1.0/board/7/configuration");
+        then.status(200).json_body(json!({
+            "columnConfig": { "columns": [{ "name": "Done", "statuses": [{ "id": "3" }] }] },
+            "estimation": { "field": { "fieldId": "customfield_10016" } }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/rest/api/3/search/jql");
+        then.status(200)
+            .json_body(json!({ "isLast": true, "issues": [
+            { "key": "ACME-1", "fields": { "status": { "id": "3" }, "customfield_10016": 4 } }
+        ] }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-1/changelog");
+        then.status(200)
+            .json_body(json!({ "isLast": true, "values": [
+            { "id": "1", "created": "2026-10-02T12:00:00.000+0000",
+              "items": [{ "field": "status", "from": "1", "to": "3" }] }
+        ] }));
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    cmd.args(["report", "burndown", "--sprint", "5", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout("Date,Remaining,Ideal\n2026-10-01,4.0,2.0\n2026-10-02,0.0,0.0\n");
+    Ok(())
+}
+
+#[test]
+fn cli_report_cfd_draws_stacked_bars_per_day() -> Result<()> {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/rest/api/3/search/jql");
+        then.status(200)
+            .json_body(json!({ "isLast": true, "issues": [
+            { "key": "ACME-1", "fields": {
+                "status": { "name": "Done", "statusCategory": { "key": "done" } },
+                "created": "2026-10-01T08:00:00.000+0000" } },
+            { "key": "ACME-2", "fields": {
+                "status": { "name": "To Do", "statusCategory": { "key": "new" } },
+                "created": "2026-10-01T09:00:00.000+0000" } }
+        ] }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-1/changelog");
+        then.status(200)
+            .json_body(json!({ "isLast": true, "values": [
+            { "id": "1", "created": "2026-10-02T10:00:00.000+0000",
+              "items": [{ "field": "status", "fromString": "To Do", "toString": "Done" }] }
+        ] }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-2/changelog");
+        then.status(200)
+            .json_body(json!({ "isLast": true, "values": [] }));
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    let bar = |done: usize, todo: usize| format!("{}{}", "█".repeat(done), "▓".repeat(todo));
+    cmd.env("TZ", "UTC")
+        .args([
+            "report",
+            "cfd",
+            "--jql",
+            "project = ACME",
+            "--from",
+            "2026-10-01",
+            "--to",
+            "2026-10-02",
+            "--format",
+            "chart",
+        ])
+        .assert()
+        .success()
+        .stdout(format!(
+            "█ Done  ▓ To Do\n2026-10-01  {}  2\n2026-10-02  {}  2\n",
+            bar(0, 40),
+            bar(20, 20)
+        ));
+    Ok(())
+}
+
+#[test]
+fn cli_report_timesheet_groups_hours_by_issue_as_csv() -> Result<()> {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/myself");
+        then.status(200)
+            .json_body(json!({ "accountId": "a1", "displayName": "Ada" }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/rest/api/3/search/jql");
+        then.status(200)
+            .json_body(json!({ "isLast": true, "issues": [
+            { "key": "ACME-1", "fields": { "summary": "Login" } },
+            { "key": "ACME-2", "fields": { "summary": "Logout" } }
+        ] }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-1/worklog");
+        then.status(200).json_body(json!({ "total": 2, "worklogs": [
+            { "author": { "accountId": "a1" }, "started": "2026-10-05T09:00:00.000+0000",
+              "timeSpentSeconds": 7200 },
+            { "author": { "accountId": "a1" }, "started": "2026-10-06T09:00:00.000+0000",
+              "timeSpentSeconds": 1800 }
+        ] }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-2/worklog");
+        then.status(200).json_body(json!({ "total": 1, "worklogs": [
+            { "author": { "accountId": "a1" }, "started": "2026-10-06T23:30:00.000+0000",
+              "timeSpentSeconds": 900 }
+        ] }));
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    cmd.args([
+        "report",
+        "timesheet",
+        "--users",
+        "me",
+        "--from",
+        "2026-10-05",
+        "--to",
+        "2026-10-09",
+        "--group-by",
+        "issue",
+        "--timezone",
+        "Asia/Tokyo",
+        "--format",
+        "csv",
+    ])
+    .assert()
+    .success()
+    .stdout("Issue,Ada,Total\nACME-1 Login,2.50,2.50\nACME-2 Logout,0.25,0.25\nTotal,2.75,2.75\n");
+    Ok(())
+}
+
+#[test]
+fn cli_standup_prints_markdown_sections() -> Result<()> {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/myself");
+        then.status(200)
+            .json_body(json!({ "accountId": "a1", "displayName": "Ada" }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/api/3/search/jql")
+            .body_contains("updatedBy");
+        then.status(200)
+            .json_body(json!({ "isLast": true, "issues": [
+            { "key": "ACME-1", "fields": { "summary": "Login", "status": { "name": "Done" } } }
+        ] }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/rest/api/3/search/jql")
+            .body_contains("statusCategory != Done");
+        then.status(200)
+            .json_body(json!({ "isLast": true, "issues": [
+            { "key": "ACME-3", "fields": { "summary": "Search",
+                "status": { "name": "Blocked", "statusCategory": { "key": "indeterminate" } } } }
+        ] }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-1/changelog");
+        then.status(200)
+            .json_body(json!({ "isLast": true, "values": [
+            { "id": "1", "author": { "accountId": "a1" }, "created": "2026-10-17T10:00:00.000+0000",
+              "items": [{ "field": "status", "fromString": "In Progress", "toString": "Done" }] }
+        ] }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-1/comment");
+        then.status(200).json_body(json!({ "comments": [] }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/rest/api/3/issue/ACME-1/worklog");
+        then.status(200).json_body(json!({ "total": 1, "worklogs": [
+            { "author": { "accountId": "a1" }, "started": "2026-10-17T09:00:00.000+0000",
+              "timeSpentSeconds": 5400 }
+        ] }));
+    });
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("jico"));
+    for (key, val) in base_env(&server) {
+        cmd.env(key, val);
+    }
+    cmd.env("TZ", "UTC")
+        .args(["standup", "--since", "2026-10-17T00:00:00Z"])
+        .assert()
+        .success()
+        .stdout(
+            "## Standup: Ada (since 2026-10-17 00:00)\n\
+             \n**Worked on**\n\
+             - ACME-1 Login — moved In Progress → Done; logged 1h 30m\n\
+             \n**In progress**\n\
+             - _Nothing_\n\
+             \n**Blocked**\n\
+             - ACME-3 Search (Blocked)\n",
+        );
+    Ok(())
+}